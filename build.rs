@@ -25,10 +25,12 @@ fn main() {
         api = Api::Gles2;
         version = (3, 2);
     }
+    extensions.push("GL_EXT_texture_filter_anisotropic");
     if cfg!(any(feature = "gles1", feature = "gles2", feature = "gles3")) {
         extensions.push("GL_OES_EGL_image");
         extensions.push("GL_OES_EGL_image_external");
         extensions.push("GL_EXT_YUV_target");
+        extensions.push("GL_EXT_disjoint_timer_query");
     }
 
     let dest = env::var("OUT_DIR").unwrap();
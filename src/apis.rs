@@ -4,7 +4,7 @@
 use crate::GLeglImageOES;
 use crate::{
     gl, Error, GLbitfield, GLboolean, GLchar, GLenum, GLfloat, GLint, GLintptr, GLsizei,
-    GLsizeiptr, GLuint,
+    GLsizeiptr, GLsync, GLuint, GLuint64,
 };
 
 use std::convert::TryInto;
@@ -13,14 +13,39 @@ use std::os::raw::c_void;
 use std::string::String;
 use std::vec::Vec;
 
+/// Check `glGetError` after `$body` and report the errors with the call
+/// `$name`, the `$arg`s and the caller location.
+#[cfg(feature = "checked")]
+macro_rules! checked {
+    ($name:ident, [$($arg:ident),*], $body:block) => {{
+        let ret = $body;
+        crate::error::check_error(
+            stringify!($name),
+            &[$((stringify!($arg), &$arg as &dyn std::fmt::Debug)),*],
+        );
+        ret
+    }};
+}
+
+/// Without the `checked` feature the `$body` is expanded as is.
+#[cfg(not(feature = "checked"))]
+macro_rules! checked {
+    ($name:ident, [$($arg:ident),*], $body:block) => {
+        $body
+    };
+}
+
 /// Set the active program object for a program pipeline object.
 ///
 /// # Parameters
 ///
 /// * `pipeline` - Specifies the program pipeline object to set the active program object for.
 /// * `program` - Specifies the program object to set as the active program pipeline object pipeline.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn active_shader_program(pipeline: GLuint, program: GLuint) {
-    unsafe { gl::ActiveShaderProgram(pipeline, program) }
+    checked!(active_shader_program, [pipeline, program], {
+        unsafe { gl::ActiveShaderProgram(pipeline, program) }
+    })
 }
 
 /// Select active texture unit.
@@ -30,8 +55,11 @@ pub fn active_shader_program(pipeline: GLuint, program: GLuint) {
 /// * `texture` - Specifies which texture unit to make active. The number of texture units is implementation dependent, but must be at least 80.
 ///   texture must be one of GL_TEXTUREi, where i ranges from zero to the value of GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS minus one.
 ///   The initial value is GL_TEXTURE0.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn active_texture(texture: GLenum) {
-    unsafe { gl::ActiveTexture(texture) }
+    checked!(active_texture, [texture], {
+        unsafe { gl::ActiveTexture(texture) }
+    })
 }
 
 /// Attaches a shader object to a program object.
@@ -40,8 +68,36 @@ pub fn active_texture(texture: GLenum) {
 ///
 /// * `program` - Specifies the program object to which a shader object will be attached.
 /// * `shader` - Specifies the shader object that is to be attached.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn attach_shader(program: GLuint, shader: GLuint) {
-    unsafe { gl::AttachShader(program, shader) }
+    checked!(attach_shader, [program, shader], {
+        unsafe { gl::AttachShader(program, shader) }
+    })
+}
+
+/// Delimit the boundaries of a query object.
+///
+/// # Parameters
+///
+/// * `target` - Specifies the target type of query object established between glBeginQuery and the subsequent glEndQuery.
+/// * `id` - Specifies the name of a query object.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn begin_query(target: GLenum, id: GLuint) {
+    checked!(begin_query, [target, id], {
+        unsafe { gl::BeginQuery(target, id) }
+    })
+}
+
+/// Start transform feedback operation.
+///
+/// # Parameters
+///
+/// * `primitive_mode` - Specifies the output type of the primitives that will be recorded into the buffer objects, GL_POINTS, GL_LINES or GL_TRIANGLES.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn begin_transform_feedback(primitive_mode: GLenum) {
+    checked!(begin_transform_feedback, [primitive_mode], {
+        unsafe { gl::BeginTransformFeedback(primitive_mode) }
+    })
 }
 
 /// Associates a generic vertex attribute index with a named attribute variable.
@@ -51,9 +107,12 @@ pub fn attach_shader(program: GLuint, shader: GLuint) {
 /// * `program` - Specifies the handle of the program object in which the association is to be made.
 /// * `index` - Specifies the index of the generic vertex attribute to be bound.
 /// * `name` - Specifies a null terminated string containing the name of the vertex shader attribute variable to which index is to be bound.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn bind_attrib_location<S: AsRef<str>>(program: GLuint, index: GLuint, name: S) {
-    let name = CString::new(name.as_ref()).unwrap();
-    unsafe { gl::BindAttribLocation(program, index, name.as_ptr()) }
+    checked!(bind_attrib_location, [program, index], {
+        let name = CString::new(name.as_ref()).unwrap();
+        unsafe { gl::BindAttribLocation(program, index, name.as_ptr()) }
+    })
 }
 
 /// Bind a named buffer object.
@@ -62,8 +121,39 @@ pub fn bind_attrib_location<S: AsRef<str>>(program: GLuint, index: GLuint, name:
 ///
 /// * `target` - Specifies the target to which the buffer object is bound, which must be one of the buffer binding targets.
 /// * `buffer` - Specifies the name of a buffer object.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn bind_buffer(target: GLenum, buffer: GLuint) {
-    unsafe { gl::BindBuffer(target, buffer) }
+    checked!(bind_buffer, [target, buffer], {
+        unsafe { gl::BindBuffer(target, buffer) }
+    })
+}
+
+/// Bind a buffer object to an indexed buffer target.
+///
+/// # Parameters
+///
+/// * `target` - Specify the target of the bind operation, e.g. GL_TRANSFORM_FEEDBACK_BUFFER or GL_UNIFORM_BUFFER.
+/// * `index` - Specify the index of the binding point within the array specified by target.
+/// * `buffer` - The name of a buffer object to bind to the specified binding point.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn bind_buffer_base(target: GLenum, index: GLuint, buffer: GLuint) {
+    checked!(bind_buffer_base, [target, index, buffer], {
+        unsafe { gl::BindBufferBase(target, index, buffer) }
+    })
+}
+
+/// Bind a range within a buffer object to an indexed buffer target.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn bind_buffer_range(
+    target: GLenum,
+    index: GLuint,
+    buffer: GLuint,
+    offset: GLintptr,
+    size: GLsizeiptr,
+) {
+    checked!(bind_buffer_range, [target, index, buffer, offset, size], {
+        unsafe { gl::BindBufferRange(target, index, buffer, offset, size) }
+    })
 }
 
 /// Bind a framebuffer to a framebuffer target.
@@ -72,11 +162,15 @@ pub fn bind_buffer(target: GLenum, buffer: GLuint) {
 ///
 /// * `target` - Specifies the framebuffer target of the binding operation.
 /// * `framebuffer` - Specifies the name of the framebuffer object to bind.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn bind_framebuffer(target: GLenum, framebuffer: GLuint) {
-    unsafe { gl::BindFramebuffer(target, framebuffer) }
+    checked!(bind_framebuffer, [target, framebuffer], {
+        unsafe { gl::BindFramebuffer(target, framebuffer) }
+    })
 }
 
 /// Bind a level of a texture to an image unit.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn bind_image_texture(
     unit: GLuint,
     texture: GLuint,
@@ -86,61 +180,126 @@ pub fn bind_image_texture(
     access: GLenum,
     format: GLenum,
 ) {
-    unsafe { gl::BindImageTexture(unit, texture, level, layered, layer, access, format) }
+    checked!(
+        bind_image_texture,
+        [unit, texture, level, layered, layer, access, format],
+        { unsafe { gl::BindImageTexture(unit, texture, level, layered, layer, access, format) } }
+    )
 }
 
 /// Bind a named renderbuffer object.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn bind_renderbuffer(target: GLenum, renderbuffer: GLuint) {
-    unsafe { gl::BindRenderbuffer(target, renderbuffer) }
+    checked!(bind_renderbuffer, [target, renderbuffer], {
+        unsafe { gl::BindRenderbuffer(target, renderbuffer) }
+    })
+}
+
+/// Bind a named sampler to a texturing target.
+///
+/// # Parameters
+///
+/// * `unit` - Specifies the index of the texture unit to which the sampler is bound.
+/// * `sampler` - Specifies the name of a sampler.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn bind_sampler(unit: GLuint, sampler: GLuint) {
+    checked!(bind_sampler, [unit, sampler], {
+        unsafe { gl::BindSampler(unit, sampler) }
+    })
 }
 
 /// Bind a named texture to a texturing target.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn bind_texture(target: GLenum, texture: GLuint) {
-    unsafe { gl::BindTexture(target, texture) }
+    checked!(bind_texture, [target, texture], {
+        unsafe { gl::BindTexture(target, texture) }
+    })
 }
 
+/// Bind a transform feedback object.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn bind_transform_feedback(target: GLenum, id: GLuint) {
+    checked!(bind_transform_feedback, [target, id], {
+        unsafe { gl::BindTransformFeedback(target, id) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn bind_vertex_array(array: GLuint) {
-    unsafe { gl::BindVertexArray(array) }
+    checked!(bind_vertex_array, [array], {
+        unsafe { gl::BindVertexArray(array) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn bind_vertex_buffer(bindingindex: GLuint, buffer: GLuint, offset: GLintptr, stride: GLsizei) {
-    unsafe { gl::BindVertexBuffer(bindingindex, buffer, offset, stride) }
+    checked!(
+        bind_vertex_buffer,
+        [bindingindex, buffer, offset, stride],
+        { unsafe { gl::BindVertexBuffer(bindingindex, buffer, offset, stride) } }
+    )
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_color(red: GLfloat, green: GLfloat, blue: GLfloat, alpha: GLfloat) {
-    unsafe { gl::BlendColor(red, green, blue, alpha) }
+    checked!(blend_color, [red, green, blue, alpha], {
+        unsafe { gl::BlendColor(red, green, blue, alpha) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_equation(mode: GLenum) {
-    unsafe { gl::BlendEquation(mode) }
+    checked!(blend_equation, [mode], {
+        unsafe { gl::BlendEquation(mode) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_equation_separate(mode_rgb: GLenum, mode_alpha: GLenum) {
-    unsafe { gl::BlendEquationSeparate(mode_rgb, mode_alpha) }
+    checked!(blend_equation_separate, [mode_rgb, mode_alpha], {
+        unsafe { gl::BlendEquationSeparate(mode_rgb, mode_alpha) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_equation_separatei(buf: GLuint, mode_rgb: GLenum, mode_alpha: GLenum) {
-    unsafe { gl::BlendEquationSeparatei(buf, mode_rgb, mode_alpha) }
+    checked!(blend_equation_separatei, [buf, mode_rgb, mode_alpha], {
+        unsafe { gl::BlendEquationSeparatei(buf, mode_rgb, mode_alpha) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_equationi(buf: GLuint, mode: GLenum) {
-    unsafe { gl::BlendEquationi(buf, mode) }
+    checked!(blend_equationi, [buf, mode], {
+        unsafe { gl::BlendEquationi(buf, mode) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_func(sfactor: GLenum, dfactor: GLenum) {
-    unsafe { gl::BlendFunc(sfactor, dfactor) }
+    checked!(blend_func, [sfactor, dfactor], {
+        unsafe { gl::BlendFunc(sfactor, dfactor) }
+    })
 }
 
 /// Specify pixel arithmetic for RGB and alpha components separately.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_func_separate(
     sfactor_rgb: GLenum,
     dfactor_rgb: GLenum,
     sfactor_alpha: GLenum,
     dfactor_alpha: GLenum,
 ) {
-    unsafe { gl::BlendFuncSeparate(sfactor_rgb, dfactor_rgb, sfactor_alpha, dfactor_alpha) }
+    checked!(
+        blend_func_separate,
+        [sfactor_rgb, dfactor_rgb, sfactor_alpha, dfactor_alpha],
+        {
+            unsafe { gl::BlendFuncSeparate(sfactor_rgb, dfactor_rgb, sfactor_alpha, dfactor_alpha) }
+        }
+    )
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_func_separatei(
     buf: GLuint,
     sfactor_rgb: GLenum,
@@ -148,11 +307,22 @@ pub fn blend_func_separatei(
     sfactor_alpha: GLenum,
     dfactor_alpha: GLenum,
 ) {
-    unsafe { gl::BlendFuncSeparatei(buf, sfactor_rgb, dfactor_rgb, sfactor_alpha, dfactor_alpha) }
+    checked!(
+        blend_func_separatei,
+        [buf, sfactor_rgb, dfactor_rgb, sfactor_alpha, dfactor_alpha],
+        {
+            unsafe {
+                gl::BlendFuncSeparatei(buf, sfactor_rgb, dfactor_rgb, sfactor_alpha, dfactor_alpha)
+            }
+        }
+    )
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn blend_funci(buf: GLuint, src: GLenum, dst: GLenum) {
-    unsafe { gl::BlendFunci(buf, src, dst) }
+    checked!(blend_funci, [buf, src, dst], {
+        unsafe { gl::BlendFunci(buf, src, dst) }
+    })
 }
 
 /// Creates and initializes a buffer object's data store.
@@ -162,187 +332,534 @@ pub fn blend_funci(buf: GLuint, src: GLenum, dst: GLenum) {
 /// * `size` - Specifies the size in bytes of the buffer object's new data store.
 /// * `data` - Specifies a pointer to data that will be copied into the data store for initialization, or NULL if no data is to be copied.
 /// * `usage` - Specifies the expected usage pattern of the data store. The symbolic constant must be GL_STREAM_DRAW, GL_STREAM_READ, GL_STREAM_COPY, GL_STATIC_DRAW, GL_STATIC_READ, GL_STATIC_COPY, GL_DYNAMIC_DRAW, GL_DYNAMIC_READ, or GL_DYNAMIC_COPY.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn buffer_data<T>(target: GLenum, size: GLsizeiptr, data: Option<&[T]>, usage: GLenum)
 where
     T: Sized,
 {
-    match data {
-        Some(v) => unsafe {
-            gl::BufferData(
-                target,
-                (v.len() * std::mem::size_of::<T>()) as GLsizeiptr,
-                v.as_ptr() as *const c_void,
-                usage,
-            );
-        },
-        None => unsafe {
-            gl::BufferData(target, size, std::ptr::null(), usage);
-        },
-    }
+    checked!(buffer_data, [target, size, usage], {
+        match data {
+            Some(v) => unsafe {
+                gl::BufferData(
+                    target,
+                    (v.len() * std::mem::size_of::<T>()) as GLsizeiptr,
+                    v.as_ptr() as *const c_void,
+                    usage,
+                );
+            },
+            None => unsafe {
+                gl::BufferData(target, size, std::ptr::null(), usage);
+            },
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn buffer_sub_data<T>(target: GLenum, offset: GLintptr, data: &[T])
 where
     T: Sized,
 {
-    unsafe {
-        gl::BufferSubData(
-            target,
-            offset,
-            (data.len() * std::mem::size_of::<T>()) as GLsizeiptr,
-            data.as_ptr() as *const c_void,
-        )
-    }
+    checked!(buffer_sub_data, [target, offset], {
+        unsafe {
+            gl::BufferSubData(
+                target,
+                offset,
+                (data.len() * std::mem::size_of::<T>()) as GLsizeiptr,
+                data.as_ptr() as *const c_void,
+            )
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn check_framebuffer_status(target: GLenum) -> GLenum {
-    unsafe { gl::CheckFramebufferStatus(target) }
+    checked!(check_framebuffer_status, [target], {
+        unsafe { gl::CheckFramebufferStatus(target) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn clear(mask: GLbitfield) {
-    unsafe { gl::Clear(mask) }
+    checked!(clear, [mask], { unsafe { gl::Clear(mask) } })
+}
+
+/// Clear a color draw buffer of the framebuffer to the float `value`.
+///
+/// # Parameters
+///
+/// * `buffer` - Specify the buffer to clear, GL_COLOR or GL_DEPTH.
+/// * `drawbuffer` - Specify a particular draw buffer to clear, 0 for GL_DEPTH.
+/// * `value` - The RGBA color, or the depth in the first element.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn clear_bufferfv(buffer: GLenum, drawbuffer: GLint, value: &[GLfloat]) {
+    checked!(clear_bufferfv, [buffer, drawbuffer, value], {
+        unsafe { gl::ClearBufferfv(buffer, drawbuffer, value.as_ptr()) }
+    })
+}
+
+/// Clear a draw buffer of the framebuffer to the signed integer `value`.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn clear_bufferiv(buffer: GLenum, drawbuffer: GLint, value: &[GLint]) {
+    checked!(clear_bufferiv, [buffer, drawbuffer, value], {
+        unsafe { gl::ClearBufferiv(buffer, drawbuffer, value.as_ptr()) }
+    })
+}
+
+/// Clear a color draw buffer of the framebuffer to the unsigned integer `value`.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn clear_bufferuiv(buffer: GLenum, drawbuffer: GLint, value: &[GLuint]) {
+    checked!(clear_bufferuiv, [buffer, drawbuffer, value], {
+        unsafe { gl::ClearBufferuiv(buffer, drawbuffer, value.as_ptr()) }
+    })
 }
 
+/// Clear the depth and stencil buffers of the framebuffer at once.
+///
+/// # Parameters
+///
+/// * `buffer` - Must be GL_DEPTH_STENCIL.
+/// * `drawbuffer` - Must be 0.
+/// * `depth` - The value to clear the depth buffer to.
+/// * `stencil` - The value to clear the stencil buffer to.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn clear_bufferfi(buffer: GLenum, drawbuffer: GLint, depth: GLfloat, stencil: GLint) {
+    checked!(clear_bufferfi, [buffer, drawbuffer, depth, stencil], {
+        unsafe { gl::ClearBufferfi(buffer, drawbuffer, depth, stencil) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn clear_color(red: GLfloat, green: GLfloat, blue: GLfloat, alpha: GLfloat) {
-    unsafe { gl::ClearColor(red, green, blue, alpha) }
+    checked!(clear_color, [red, green, blue, alpha], {
+        unsafe { gl::ClearColor(red, green, blue, alpha) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn clear_depthf(d: GLfloat) {
-    unsafe { gl::ClearDepthf(d) }
+    checked!(clear_depthf, [d], { unsafe { gl::ClearDepthf(d) } })
 }
 
-#[cfg(feature = "gl4")]
-pub fn clear_index(index: GLfloat) {
-    unsafe { gl::ClearIndex(index) }
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn clear_stencil(s: GLint) {
+    checked!(clear_stencil, [s], { unsafe { gl::ClearStencil(s) } })
 }
 
-pub fn clear_stencil(s: GLint) {
-    unsafe { gl::ClearStencil(s) }
+/// Start conditional rendering, the rendering is discarded if the query reported no samples passed.
+///
+/// # Parameters
+///
+/// * `id` - Specifies the name of an occlusion query object whose results are used to determine if the rendering commands are discarded.
+/// * `mode` - Specifies how glBeginConditionalRender interprets the results of the occlusion query.
+#[cfg(any(feature = "gl3", feature = "gl4"))]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn begin_conditional_render(id: GLuint, mode: GLenum) {
+    checked!(begin_conditional_render, [id, mode], {
+        unsafe { gl::BeginConditionalRender(id, mode) }
+    })
 }
 
+/// Block and wait for a sync object to become signaled.
+///
+/// # Parameters
+///
+/// * `sync` - The sync object whose status to wait on.
+/// * `flags` - A bitfield controlling the command flushing behavior, may be GL_SYNC_FLUSH_COMMANDS_BIT.
+/// * `timeout` - The timeout, specified in nanoseconds, for which the implementation should wait.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn client_wait_sync(sync: GLsync, flags: GLbitfield, timeout: GLuint64) -> GLenum {
+    checked!(client_wait_sync, [sync, flags, timeout], {
+        unsafe { gl::ClientWaitSync(sync, flags, timeout) }
+    })
+}
+
+/// Enable and disable writing of frame buffer color components.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn color_mask(red: GLboolean, green: GLboolean, blue: GLboolean, alpha: GLboolean) {
+    checked!(color_mask, [red, green, blue, alpha], {
+        unsafe { gl::ColorMask(red, green, blue, alpha) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn compile_shader(shader: GLuint) {
-    unsafe { gl::CompileShader(shader) }
+    checked!(compile_shader, [shader], {
+        unsafe { gl::CompileShader(shader) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn create_program() -> Result<GLuint, Error> {
-    match unsafe { gl::CreateProgram() } {
-        gl::NONE => Err(Error::new()),
-        val => Ok(val),
-    }
+    checked!(create_program, [], {
+        match unsafe { gl::CreateProgram() } {
+            gl::NONE => Err(Error::new()),
+            val => Ok(val),
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn create_shader(type_: GLenum) -> Result<GLuint, Error> {
-    match unsafe { gl::CreateShader(type_) } {
-        gl::NONE => Err(Error::new()),
-        val => Ok(val),
-    }
+    checked!(create_shader, [type_], {
+        match unsafe { gl::CreateShader(type_) } {
+            gl::NONE => Err(Error::new()),
+            val => Ok(val),
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn create_shader_programv<T: AsRef<str>>(
     type_: GLenum,
     strings: &[T],
 ) -> Result<GLuint, Error> {
-    let cv: Vec<CString> = strings
-        .iter()
-        .map(|s| CString::new(s.as_ref()).unwrap())
-        .collect();
-    let pv: Vec<*const GLchar> = cv.iter().map(|s| s.as_ptr()).collect();
-    match unsafe { gl::CreateShaderProgramv(type_, pv.len().try_into().unwrap(), pv.as_ptr()) } {
-        gl::NONE => Err(Error::new()),
-        val => Ok(val),
-    }
+    checked!(create_shader_programv, [type_], {
+        let cv: Vec<CString> = strings
+            .iter()
+            .map(|s| CString::new(s.as_ref()).unwrap())
+            .collect();
+        let pv: Vec<*const GLchar> = cv.iter().map(|s| s.as_ptr()).collect();
+        match unsafe { gl::CreateShaderProgramv(type_, pv.len().try_into().unwrap(), pv.as_ptr()) }
+        {
+            gl::NONE => Err(Error::new()),
+            val => Ok(val),
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn cull_face(mode: GLenum) {
-    unsafe { gl::CullFace(mode) }
+    checked!(cull_face, [mode], { unsafe { gl::CullFace(mode) } })
+}
+
+/// Set the callback receiving the debug messages, pass `None` to remove it.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn debug_message_callback(callback: gl::GLDEBUGPROC, user_param: *const c_void) {
+    checked!(debug_message_callback, [callback, user_param], {
+        unsafe { gl::DebugMessageCallback(callback, user_param) }
+    })
+}
+
+/// Enable or disable the debug messages matching the filter.
+///
+/// # Parameters
+///
+/// * `source`, `type_`, `severity` - The message properties to match, `GL_DONT_CARE` matches all.
+/// * `ids` - The message Ids to match, empty matches all.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn debug_message_control(
+    source: GLenum,
+    type_: GLenum,
+    severity: GLenum,
+    ids: &[GLuint],
+    enabled: bool,
+) {
+    checked!(
+        debug_message_control,
+        [source, type_, severity, ids, enabled],
+        {
+            unsafe {
+                gl::DebugMessageControl(
+                    source,
+                    type_,
+                    severity,
+                    ids.len() as GLsizei,
+                    ids.as_ptr(),
+                    enabled as GLboolean,
+                )
+            }
+        }
+    )
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn debug_message_insert(
+    source: GLenum,
+    type_: GLenum,
+    id: GLuint,
+    severity: GLenum,
+    message: &str,
+) {
+    checked!(
+        debug_message_insert,
+        [source, type_, id, severity, message],
+        {
+            unsafe {
+                gl::DebugMessageInsert(
+                    source,
+                    type_,
+                    id,
+                    severity,
+                    message.len() as GLsizei,
+                    message.as_ptr() as *const GLchar,
+                )
+            }
+        }
+    )
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn delete_buffers(buffers: &[GLuint]) {
-    unsafe { gl::DeleteBuffers(buffers.len() as GLsizei, buffers.as_ptr()) }
+    checked!(delete_buffers, [buffers], {
+        unsafe { gl::DeleteBuffers(buffers.len() as GLsizei, buffers.as_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn delete_framebuffers(framebuffers: &[GLuint]) {
-    unsafe { gl::DeleteFramebuffers(framebuffers.len() as GLsizei, framebuffers.as_ptr()) }
+    checked!(delete_framebuffers, [framebuffers], {
+        unsafe { gl::DeleteFramebuffers(framebuffers.len() as GLsizei, framebuffers.as_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn delete_program(program: GLuint) {
-    unsafe { gl::DeleteProgram(program) }
+    checked!(delete_program, [program], {
+        unsafe { gl::DeleteProgram(program) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn delete_queries(ids: &[GLuint]) {
+    checked!(delete_queries, [ids], {
+        unsafe { gl::DeleteQueries(ids.len() as GLsizei, ids.as_ptr()) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn delete_renderbuffers(renderbuffers: &[GLuint]) {
+    checked!(delete_renderbuffers, [renderbuffers], {
+        unsafe { gl::DeleteRenderbuffers(renderbuffers.len() as GLsizei, renderbuffers.as_ptr()) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn delete_samplers(samplers: &[GLuint]) {
+    checked!(delete_samplers, [samplers], {
+        unsafe { gl::DeleteSamplers(samplers.len() as GLsizei, samplers.as_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn delete_shader(shader: GLuint) {
-    unsafe { gl::DeleteShader(shader) }
+    checked!(delete_shader, [shader], {
+        unsafe { gl::DeleteShader(shader) }
+    })
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn delete_sync(sync: GLsync) {
+    checked!(delete_sync, [sync], { unsafe { gl::DeleteSync(sync) } })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn delete_textures(textures: &[GLuint]) {
-    unsafe { gl::DeleteTextures(textures.len() as GLsizei, textures.as_ptr()) }
+    checked!(delete_textures, [textures], {
+        unsafe { gl::DeleteTextures(textures.len() as GLsizei, textures.as_ptr()) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn delete_transform_feedbacks(ids: &[GLuint]) {
+    checked!(delete_transform_feedbacks, [ids], {
+        unsafe { gl::DeleteTransformFeedbacks(ids.len() as GLsizei, ids.as_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn delete_vertex_arrays(arrays: &[GLuint]) {
-    unsafe { gl::DeleteVertexArrays(arrays.len() as GLsizei, arrays.as_ptr()) }
+    checked!(delete_vertex_arrays, [arrays], {
+        unsafe { gl::DeleteVertexArrays(arrays.len() as GLsizei, arrays.as_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn depth_func(func: GLenum) {
-    unsafe { gl::DepthFunc(func) }
+    checked!(depth_func, [func], { unsafe { gl::DepthFunc(func) } })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn depth_mask(flag: GLboolean) {
-    unsafe { gl::DepthMask(flag) }
+    checked!(depth_mask, [flag], { unsafe { gl::DepthMask(flag) } })
+}
+
+/// Specify mapping of depth values from normalized device coordinates to window coordinates.
+///
+/// # Parameters
+///
+/// * `n` - Specifies the mapping of the near clipping plane to window coordinates. The initial value is 0.
+/// * `f` - Specifies the mapping of the far clipping plane to window coordinates. The initial value is 1.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn depth_rangef(n: GLfloat, f: GLfloat) {
+    checked!(depth_rangef, [n, f], { unsafe { gl::DepthRangef(n, f) } })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn detach_shader(program: GLuint, shader: GLuint) {
-    unsafe { gl::DetachShader(program, shader) }
+    checked!(detach_shader, [program, shader], {
+        unsafe { gl::DetachShader(program, shader) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn disable(cap: GLenum) {
-    unsafe { gl::Disable(cap) }
+    checked!(disable, [cap], { unsafe { gl::Disable(cap) } })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn disable_vertex_attrib_array(index: GLuint) {
-    unsafe { gl::DisableVertexAttribArray(index) }
+    checked!(disable_vertex_attrib_array, [index], {
+        unsafe { gl::DisableVertexAttribArray(index) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn draw_arrays(mode: GLenum, first: GLint, count: GLsizei) {
-    unsafe { gl::DrawArrays(mode, first, count) }
+    checked!(draw_arrays, [mode, first, count], {
+        unsafe { gl::DrawArrays(mode, first, count) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn draw_buffers(bufs: &[GLenum]) {
-    unsafe { gl::DrawBuffers(bufs.len() as GLsizei, bufs.as_ptr()) }
+    checked!(draw_buffers, [bufs], {
+        unsafe { gl::DrawBuffers(bufs.len() as GLsizei, bufs.as_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn draw_elements(mode: GLenum, count: GLsizei, type_: GLenum, indices: GLsizeiptr) {
-    unsafe { gl::DrawElements(mode, count, type_, indices as *const c_void) }
+    checked!(draw_elements, [mode, count, type_, indices], {
+        unsafe { gl::DrawElements(mode, count, type_, indices as *const c_void) }
+    })
 }
 
 #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn egl_image_target_renderbuffer_storage_oes(target: GLenum, image: GLeglImageOES) {
-    unsafe { gl::EGLImageTargetRenderbufferStorageOES(target, image) }
+    checked!(
+        egl_image_target_renderbuffer_storage_oes,
+        [target, image],
+        { unsafe { gl::EGLImageTargetRenderbufferStorageOES(target, image) } }
+    )
 }
 
 #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn egl_image_target_texture_2d_oes(target: GLenum, image: GLeglImageOES) {
-    unsafe { gl::EGLImageTargetTexture2DOES(target, image) }
+    checked!(egl_image_target_texture_2d_oes, [target, image], {
+        unsafe { gl::EGLImageTargetTexture2DOES(target, image) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn enable(cap: GLenum) {
-    unsafe { gl::Enable(cap) }
+    checked!(enable, [cap], { unsafe { gl::Enable(cap) } })
+}
+
+#[cfg(any(feature = "gl3", feature = "gl4"))]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn end_conditional_render() {
+    checked!(end_conditional_render, [], {
+        unsafe { gl::EndConditionalRender() }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn end_transform_feedback() {
+    checked!(end_transform_feedback, [], {
+        unsafe { gl::EndTransformFeedback() }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn end_query(target: GLenum) {
+    checked!(end_query, [target], { unsafe { gl::EndQuery(target) } })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn enable_vertex_attrib_array(index: GLuint) {
-    unsafe { gl::EnableVertexAttribArray(index) }
+    checked!(enable_vertex_attrib_array, [index], {
+        unsafe { gl::EnableVertexAttribArray(index) }
+    })
 }
 
+/// Create a new sync object and insert it into the GL command stream.
+///
+/// # Parameters
+///
+/// * `condition` - Specifies the condition that must be met to set the sync object's state to signaled, must be GL_SYNC_GPU_COMMANDS_COMPLETE.
+/// * `flags` - Specifies a bitwise combination of flags controlling the behavior of the sync object, must be 0.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn fence_sync(condition: GLenum, flags: GLbitfield) -> GLsync {
+    checked!(fence_sync, [condition, flags], {
+        unsafe { gl::FenceSync(condition, flags) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn finish() {
-    unsafe { gl::Finish() }
+    checked!(finish, [], { unsafe { gl::Finish() } })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn flush() {
-    unsafe { gl::Flush() }
+    checked!(flush, [], { unsafe { gl::Flush() } })
+}
+
+/// Indicate modifications to a range of a mapped buffer.
+///
+/// # Parameters
+///
+/// * `target` - Specifies the target to which the buffer object is bound.
+/// * `offset` - Specifies the start of the buffer subrange, in basic machine units.
+/// * `length` - Specifies the length of the buffer subrange, in basic machine units.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn flush_mapped_buffer_range(target: GLenum, offset: GLintptr, length: GLsizeiptr) {
+    checked!(flush_mapped_buffer_range, [target, offset, length], {
+        unsafe { gl::FlushMappedBufferRange(target, offset, length) }
+    })
+}
+
+/// Attach a renderbuffer object to a framebuffer object.
+///
+/// # Parameters
+///
+/// * `target` - Specifies the framebuffer target.
+/// * `attachment` - Specifies the attachment point of the framebuffer.
+/// * `renderbuffertarget` - Specifies the renderbuffer target, must be GL_RENDERBUFFER.
+/// * `renderbuffer` - Specifies the name of an existing renderbuffer object to attach.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn framebuffer_renderbuffer(
+    target: GLenum,
+    attachment: GLenum,
+    renderbuffertarget: GLenum,
+    renderbuffer: GLuint,
+) {
+    checked!(
+        framebuffer_renderbuffer,
+        [target, attachment, renderbuffertarget, renderbuffer],
+        {
+            unsafe {
+                gl::FramebufferRenderbuffer(target, attachment, renderbuffertarget, renderbuffer)
+            }
+        }
+    )
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn framebuffer_texture(target: GLenum, attachment: GLenum, texture: GLuint, level: GLint) {
-    unsafe { gl::FramebufferTexture(target, attachment, texture, level) }
+    checked!(framebuffer_texture, [target, attachment, texture, level], {
+        unsafe { gl::FramebufferTexture(target, attachment, texture, level) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn framebuffer_texture2d(
     target: GLenum,
     attachment: GLenum,
@@ -350,384 +867,963 @@ pub fn framebuffer_texture2d(
     texture: GLuint,
     level: GLint,
 ) {
-    unsafe { gl::FramebufferTexture2D(target, attachment, textarget, texture, level) }
+    checked!(
+        framebuffer_texture2d,
+        [target, attachment, textarget, texture, level],
+        { unsafe { gl::FramebufferTexture2D(target, attachment, textarget, texture, level) } }
+    )
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn front_face(mode: GLenum) {
-    unsafe { gl::FrontFace(mode) }
+    checked!(front_face, [mode], { unsafe { gl::FrontFace(mode) } })
+}
+
+/// Map all or part of a buffer object's data store into the client's address space.
+///
+/// # Parameters
+///
+/// * `target` - Specifies the target to which the buffer object is bound.
+/// * `offset` - Specifies the starting offset within the buffer of the range to be mapped.
+/// * `length` - Specifies the length of the range to be mapped.
+/// * `access` - Specifies a combination of access flags indicating the desired access to the mapped range.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn map_buffer_range(
+    target: GLenum,
+    offset: GLintptr,
+    length: GLsizeiptr,
+    access: GLbitfield,
+) -> *mut c_void {
+    checked!(map_buffer_range, [target, offset, length, access], {
+        unsafe { gl::MapBufferRange(target, offset, length, access) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn new_buffer() -> GLuint {
-    let mut buffers: [GLuint; 1] = [0];
-    gen_buffers(&mut buffers);
-    buffers[0]
+    checked!(new_buffer, [], {
+        let mut buffers: [GLuint; 1] = [0];
+        gen_buffers(&mut buffers);
+        buffers[0]
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn gen_buffers(buffers: &mut [GLuint]) {
-    unsafe { gl::GenBuffers(buffers.len() as GLsizei, buffers.as_mut_ptr()) }
+    checked!(gen_buffers, [buffers], {
+        unsafe { gl::GenBuffers(buffers.len() as GLsizei, buffers.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn gen_framebuffers(framebuffers: &mut [GLuint]) {
-    unsafe { gl::GenFramebuffers(framebuffers.len() as GLsizei, framebuffers.as_mut_ptr()) }
+    checked!(gen_framebuffers, [framebuffers], {
+        unsafe { gl::GenFramebuffers(framebuffers.len() as GLsizei, framebuffers.as_mut_ptr()) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn new_query() -> GLuint {
+    checked!(new_query, [], {
+        let mut ids: [GLuint; 1] = [0];
+        gen_queries(&mut ids);
+        ids[0]
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn gen_queries(ids: &mut [GLuint]) {
+    checked!(gen_queries, [ids], {
+        unsafe { gl::GenQueries(ids.len() as GLsizei, ids.as_mut_ptr()) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn new_renderbuffer() -> GLuint {
+    checked!(new_renderbuffer, [], {
+        let mut renderbuffers: [GLuint; 1] = [0];
+        gen_renderbuffers(&mut renderbuffers);
+        renderbuffers[0]
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn gen_renderbuffers(renderbuffers: &mut [GLuint]) {
+    checked!(gen_renderbuffers, [renderbuffers], {
+        unsafe { gl::GenRenderbuffers(renderbuffers.len() as GLsizei, renderbuffers.as_mut_ptr()) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn new_sampler() -> GLuint {
+    checked!(new_sampler, [], {
+        let mut samplers: [GLuint; 1] = [0];
+        gen_samplers(&mut samplers);
+        samplers[0]
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn gen_samplers(samplers: &mut [GLuint]) {
+    checked!(gen_samplers, [samplers], {
+        unsafe { gl::GenSamplers(samplers.len() as GLsizei, samplers.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn new_texture() -> GLuint {
-    let mut textures: [GLuint; 1] = [0];
-    gen_textures(&mut textures);
-    textures[0]
+    checked!(new_texture, [], {
+        let mut textures: [GLuint; 1] = [0];
+        gen_textures(&mut textures);
+        textures[0]
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn gen_textures(textures: &mut [GLuint]) {
-    unsafe { gl::GenTextures(textures.len() as GLsizei, textures.as_mut_ptr()) }
+    checked!(gen_textures, [textures], {
+        unsafe { gl::GenTextures(textures.len() as GLsizei, textures.as_mut_ptr()) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn new_transform_feedback() -> GLuint {
+    checked!(new_transform_feedback, [], {
+        let mut ids: [GLuint; 1] = [0];
+        gen_transform_feedbacks(&mut ids);
+        ids[0]
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn gen_transform_feedbacks(ids: &mut [GLuint]) {
+    checked!(gen_transform_feedbacks, [ids], {
+        unsafe { gl::GenTransformFeedbacks(ids.len() as GLsizei, ids.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn new_vertex_array() -> GLuint {
-    let mut arrays: [GLuint; 1] = [0];
-    gen_vertex_arrays(&mut arrays);
-    arrays[0]
+    checked!(new_vertex_array, [], {
+        let mut arrays: [GLuint; 1] = [0];
+        gen_vertex_arrays(&mut arrays);
+        arrays[0]
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn gen_vertex_arrays(arrays: &mut [GLuint]) {
-    unsafe { gl::GenVertexArrays(arrays.len() as GLsizei, arrays.as_mut_ptr()) }
+    checked!(gen_vertex_arrays, [arrays], {
+        unsafe { gl::GenVertexArrays(arrays.len() as GLsizei, arrays.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn generate_mipmap(target: GLenum) {
-    unsafe { gl::GenerateMipmap(target) }
+    checked!(generate_mipmap, [target], {
+        unsafe { gl::GenerateMipmap(target) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_active_attrib(program: GLuint, index: GLuint) -> Result<(String, GLenum, GLint), Error> {
-    let mut name: [u8; 256] = [0; 256];
-    let buf_size: GLsizei = 255;
-    let mut length: GLsizei = 0;
-    let mut size: GLint = 0;
-    let mut type_: GLenum = 0;
-    unsafe {
-        gl::GetActiveAttrib(
-            program,
-            index,
-            buf_size,
-            &mut length,
-            &mut size,
-            &mut type_,
-            name.as_mut_ptr() as *mut GLchar,
-        )
-    }
-    match length {
-        0 => Err(Error::new()),
-        _ => Ok((
-            CStr::from_bytes_with_nul(&name)
-                .unwrap()
-                .to_string_lossy()
-                .into_owned(),
-            type_,
-            size,
-        )),
-    }
+    checked!(get_active_attrib, [program, index], {
+        let mut name: [u8; 256] = [0; 256];
+        let buf_size: GLsizei = 255;
+        let mut length: GLsizei = 0;
+        let mut size: GLint = 0;
+        let mut type_: GLenum = 0;
+        unsafe {
+            gl::GetActiveAttrib(
+                program,
+                index,
+                buf_size,
+                &mut length,
+                &mut size,
+                &mut type_,
+                name.as_mut_ptr() as *mut GLchar,
+            )
+        }
+        match length {
+            0 => Err(Error::new()),
+            _ => Ok((
+                CStr::from_bytes_with_nul(&name)
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned(),
+                type_,
+                size,
+            )),
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_active_uniform(
     program: GLuint,
     index: GLuint,
 ) -> Result<(String, GLenum, GLint), Error> {
-    let mut name: [u8; 256] = [0; 256];
-    let buf_size: GLsizei = 255;
-    let mut length: GLsizei = 0;
-    let mut size: GLint = 0;
-    let mut type_: GLenum = 0;
-    unsafe {
-        gl::GetActiveUniform(
-            program,
-            index,
-            buf_size,
-            &mut length,
-            &mut size,
-            &mut type_,
-            name.as_mut_ptr() as *mut GLchar,
-        )
-    }
-    match length {
-        0 => Err(Error::new()),
-        _ => Ok((
-            CStr::from_bytes_with_nul(&name)
-                .unwrap()
-                .to_string_lossy()
-                .into_owned(),
-            type_,
-            size,
-        )),
-    }
+    checked!(get_active_uniform, [program, index], {
+        let mut name: [u8; 256] = [0; 256];
+        let buf_size: GLsizei = 255;
+        let mut length: GLsizei = 0;
+        let mut size: GLint = 0;
+        let mut type_: GLenum = 0;
+        unsafe {
+            gl::GetActiveUniform(
+                program,
+                index,
+                buf_size,
+                &mut length,
+                &mut size,
+                &mut type_,
+                name.as_mut_ptr() as *mut GLchar,
+            )
+        }
+        match length {
+            0 => Err(Error::new()),
+            _ => Ok((
+                CStr::from_bytes_with_nul(&name)
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned(),
+                type_,
+                size,
+            )),
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_attached_shaders(program: GLuint, max_count: GLsizei) -> Result<Vec<GLuint>, Error> {
-    let mut count: GLsizei = 0;
-    let mut shaders: Vec<GLuint> = vec![0, max_count as u32];
-    unsafe { gl::GetAttachedShaders(program, max_count, &mut count, shaders.as_mut_ptr()) }
-    match count {
-        0 => Err(Error::new()),
-        v => {
-            shaders.truncate(v as usize);
-            Ok(shaders)
+    checked!(get_attached_shaders, [program, max_count], {
+        let mut count: GLsizei = 0;
+        let mut shaders: Vec<GLuint> = vec![0, max_count as u32];
+        unsafe { gl::GetAttachedShaders(program, max_count, &mut count, shaders.as_mut_ptr()) }
+        match count {
+            0 => Err(Error::new()),
+            v => {
+                shaders.truncate(v as usize);
+                Ok(shaders)
+            }
         }
-    }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_attrib_location<T: AsRef<str>>(program: GLuint, name: T) -> Result<GLint, Error> {
-    let name = CString::new(name.as_ref()).unwrap();
-    let val = unsafe { gl::GetAttribLocation(program, name.as_ptr()) };
-    match val {
-        -1 => Err(Error::new()),
-        x => Ok(x),
-    }
+    checked!(get_attrib_location, [program], {
+        let name = CString::new(name.as_ref()).unwrap();
+        let val = unsafe { gl::GetAttribLocation(program, name.as_ptr()) };
+        match val {
+            -1 => Err(Error::new()),
+            x => Ok(x),
+        }
+    })
 }
 
 pub fn get_error() -> GLenum {
     unsafe { gl::GetError() }
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn get_booleanv(pname: GLenum, data: &mut [GLboolean]) {
+    checked!(get_booleanv, [pname, data], {
+        unsafe { gl::GetBooleanv(pname, data.as_mut_ptr()) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_floatv(pname: GLenum, data: &mut [GLfloat]) {
-    unsafe { gl::GetFloatv(pname, data.as_mut_ptr()) }
+    checked!(get_floatv, [pname, data], {
+        unsafe { gl::GetFloatv(pname, data.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_integerv(pname: GLenum, data: &mut [GLint]) {
-    unsafe { gl::GetIntegerv(pname, data.as_mut_ptr()) }
+    checked!(get_integerv, [pname, data], {
+        unsafe { gl::GetIntegerv(pname, data.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_pointerv(pname: GLenum) -> *mut c_void {
-    let ptr: *mut c_void = std::ptr::null_mut();
-    unsafe {
-        gl::GetPointerv(pname, &ptr);
-    }
-    ptr
+    checked!(get_pointerv, [pname], {
+        let ptr: *mut c_void = std::ptr::null_mut();
+        unsafe {
+            gl::GetPointerv(pname, &ptr);
+        }
+        ptr
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn get_object_label(identifier: GLenum, name: GLuint) -> String {
+    checked!(get_object_label, [identifier, name], {
+        let mut buf_size: GLint = 0;
+        get_integerv(gl::MAX_LABEL_LENGTH, std::slice::from_mut(&mut buf_size));
+        let mut label: Vec<u8> = vec![0; buf_size.max(1) as usize];
+        let mut length: GLsizei = 0;
+        unsafe {
+            gl::GetObjectLabel(
+                identifier,
+                name,
+                label.len() as GLsizei,
+                &mut length,
+                label.as_mut_ptr() as *mut GLchar,
+            );
+        }
+        label.truncate(length.max(0) as usize);
+        String::from_utf8_lossy(&label).into_owned()
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_program_binary(program: GLuint) -> Result<(GLenum, Vec<u8>), Error> {
-    let buf_size = get_programiv(program, gl::PROGRAM_BINARY_LENGTH);
-    let mut data: Vec<u8> = vec![0; (buf_size + 2) as usize];
-    let mut length: GLsizei = 0;
-    let mut binary_format: GLenum = 0;
-    unsafe {
-        gl::GetProgramBinary(
-            program,
-            data.len() as GLsizei,
-            &mut length,
-            &mut binary_format,
-            data.as_mut_ptr() as *mut c_void,
-        );
-    }
-    match length {
-        0 => Err(Error::new()),
-        x => {
-            data.truncate(x as usize);
-            Ok((binary_format, data))
+    checked!(get_program_binary, [program], {
+        let buf_size = get_programiv(program, gl::PROGRAM_BINARY_LENGTH);
+        let mut data: Vec<u8> = vec![0; (buf_size + 2) as usize];
+        let mut length: GLsizei = 0;
+        let mut binary_format: GLenum = 0;
+        unsafe {
+            gl::GetProgramBinary(
+                program,
+                data.len() as GLsizei,
+                &mut length,
+                &mut binary_format,
+                data.as_mut_ptr() as *mut c_void,
+            );
         }
-    }
+        match length {
+            0 => Err(Error::new()),
+            x => {
+                data.truncate(x as usize);
+                Ok((binary_format, data))
+            }
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_program_info_log(program: GLuint) -> Result<String, Error> {
-    let buf_size = get_programiv(program, gl::INFO_LOG_LENGTH);
-    let mut info: Vec<u8> = vec![0; buf_size as usize];
-    let mut length: GLsizei = 0;
-    unsafe {
-        gl::GetProgramInfoLog(
-            program,
-            info.len() as GLsizei,
-            &mut length,
-            info.as_mut_ptr() as *mut GLchar,
-        );
-    }
-    match length {
-        0 => Err(Error::new()),
-        _ => Ok(CStr::from_bytes_with_nul(&info)
-            .unwrap()
-            .to_string_lossy()
-            .into_owned()),
-    }
+    checked!(get_program_info_log, [program], {
+        let buf_size = get_programiv(program, gl::INFO_LOG_LENGTH);
+        let mut info: Vec<u8> = vec![0; buf_size as usize];
+        let mut length: GLsizei = 0;
+        unsafe {
+            gl::GetProgramInfoLog(
+                program,
+                info.len() as GLsizei,
+                &mut length,
+                info.as_mut_ptr() as *mut GLchar,
+            );
+        }
+        match length {
+            0 => Err(Error::new()),
+            _ => Ok(CStr::from_bytes_with_nul(&info)
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()),
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_programiv(program: GLuint, pname: GLenum) -> GLint {
-    let mut params: GLint = 0;
-    unsafe {
-        gl::GetProgramiv(program, pname, &mut params);
-    }
-    params
+    checked!(get_programiv, [program, pname], {
+        let mut params: GLint = 0;
+        unsafe {
+            gl::GetProgramiv(program, pname, &mut params);
+        }
+        params
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn get_renderbuffer_parameteriv(target: GLenum, pname: GLenum) -> GLint {
+    checked!(get_renderbuffer_parameteriv, [target, pname], {
+        let mut params: GLint = 0;
+        unsafe {
+            gl::GetRenderbufferParameteriv(target, pname, &mut params);
+        }
+        params
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_shader_info_log(shader: GLuint) -> Result<String, Error> {
-    let buf_size = get_shaderiv(shader, gl::INFO_LOG_LENGTH);
-    let mut info: Vec<u8> = vec![0; buf_size as usize];
-    let mut length: GLsizei = 0;
-    unsafe {
-        gl::GetShaderInfoLog(
-            shader,
-            info.len() as GLsizei,
-            &mut length,
-            info.as_mut_ptr() as *mut GLchar,
-        );
-    }
-    match length {
-        0 => Err(Error::new()),
-        _ => Ok(CStr::from_bytes_with_nul(&info)
-            .unwrap()
-            .to_string_lossy()
-            .into_owned()),
-    }
+    checked!(get_shader_info_log, [shader], {
+        let buf_size = get_shaderiv(shader, gl::INFO_LOG_LENGTH);
+        let mut info: Vec<u8> = vec![0; buf_size as usize];
+        let mut length: GLsizei = 0;
+        unsafe {
+            gl::GetShaderInfoLog(
+                shader,
+                info.len() as GLsizei,
+                &mut length,
+                info.as_mut_ptr() as *mut GLchar,
+            );
+        }
+        match length {
+            0 => Err(Error::new()),
+            _ => Ok(CStr::from_bytes_with_nul(&info)
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()),
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_shader_source(shader: GLuint) -> Result<String, Error> {
-    let buf_size = get_shaderiv(shader, gl::SHADER_SOURCE_LENGTH);
-    let mut source: Vec<u8> = vec![0; (buf_size + 2) as usize];
-    let mut length: GLsizei = 0;
-    unsafe {
-        gl::GetShaderSource(
-            shader,
-            source.len() as GLsizei,
-            &mut length,
-            source.as_mut_ptr() as *mut GLchar,
-        );
-    }
-    match length {
-        0 => Err(Error::new()),
-        _ => Ok(CStr::from_bytes_with_nul(&source)
-            .unwrap()
-            .to_string_lossy()
-            .into_owned()),
-    }
+    checked!(get_shader_source, [shader], {
+        let buf_size = get_shaderiv(shader, gl::SHADER_SOURCE_LENGTH);
+        let mut source: Vec<u8> = vec![0; (buf_size + 2) as usize];
+        let mut length: GLsizei = 0;
+        unsafe {
+            gl::GetShaderSource(
+                shader,
+                source.len() as GLsizei,
+                &mut length,
+                source.as_mut_ptr() as *mut GLchar,
+            );
+        }
+        match length {
+            0 => Err(Error::new()),
+            _ => Ok(CStr::from_bytes_with_nul(&source)
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()),
+        }
+    })
 }
 
 /// Return a parameter from a shader object.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_shaderiv(shader: GLuint, pname: GLenum) -> GLint {
-    let mut params: GLint = 0;
-    unsafe {
-        gl::GetShaderiv(shader, pname, &mut params);
-    }
-    params
+    checked!(get_shaderiv, [shader, pname], {
+        let mut params: GLint = 0;
+        unsafe {
+            gl::GetShaderiv(shader, pname, &mut params);
+        }
+        params
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_string(name: GLenum) -> Result<String, Error> {
-    unsafe {
-        let name: *const GLchar = gl::GetString(name) as *const GLchar;
-        if name.is_null() {
-            Err(Error::new())
-        } else {
-            Ok(CStr::from_ptr(name).to_string_lossy().into_owned())
+    checked!(get_string, [name], {
+        unsafe {
+            let name: *const GLchar = gl::GetString(name) as *const GLchar;
+            if name.is_null() {
+                Err(Error::new())
+            } else {
+                Ok(CStr::from_ptr(name).to_string_lossy().into_owned())
+            }
         }
-    }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_stringi(name: GLenum, index: GLuint) -> Result<String, Error> {
-    unsafe {
-        let name: *const GLchar = gl::GetStringi(name, index) as *const GLchar;
-        if name.is_null() {
-            Err(Error::new())
-        } else {
-            Ok(CStr::from_ptr(name).to_string_lossy().into_owned())
+    checked!(get_stringi, [name, index], {
+        unsafe {
+            let name: *const GLchar = gl::GetStringi(name, index) as *const GLchar;
+            if name.is_null() {
+                Err(Error::new())
+            } else {
+                Ok(CStr::from_ptr(name).to_string_lossy().into_owned())
+            }
         }
-    }
+    })
+}
+
+/// Return parameters of a query object.
+///
+/// # Parameters
+///
+/// * `id` - Specifies the name of a query object.
+/// * `pname` - Specifies the symbolic name of a query object parameter, GL_QUERY_RESULT or GL_QUERY_RESULT_AVAILABLE.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn get_query_objectuiv(id: GLuint, pname: GLenum) -> GLuint {
+    checked!(get_query_objectuiv, [id, pname], {
+        let mut params: GLuint = 0;
+        unsafe {
+            gl::GetQueryObjectuiv(id, pname, &mut params);
+        }
+        params
+    })
+}
+
+/// Return 64 bits parameters of a query object, e.g. the result of timer queries.
+#[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn get_query_objectui64v(id: GLuint, pname: GLenum) -> GLuint64 {
+    checked!(get_query_objectui64v, [id, pname], {
+        let mut params: GLuint64 = 0;
+        unsafe {
+            gl::GetQueryObjectui64vEXT(id, pname, &mut params);
+        }
+        params
+    })
+}
+
+/// Return 64 bits parameters of a query object, e.g. the result of timer queries.
+#[cfg(not(any(feature = "gles1", feature = "gles2", feature = "gles3")))]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn get_query_objectui64v(id: GLuint, pname: GLenum) -> GLuint64 {
+    checked!(get_query_objectui64v, [id, pname], {
+        let mut params: GLuint64 = 0;
+        unsafe {
+            gl::GetQueryObjectui64v(id, pname, &mut params);
+        }
+        params
+    })
+}
+
+/// Query the properties of a sync object.
+///
+/// # Parameters
+///
+/// * `sync` - Specifies the sync object whose properties to query.
+/// * `pname` - Specifies the parameter whose value to retrieve from the sync object, such as GL_SYNC_STATUS.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn get_synciv(sync: GLsync, pname: GLenum) -> GLint {
+    checked!(get_synciv, [sync, pname], {
+        let mut value: GLint = 0;
+        let mut length: GLsizei = 0;
+        unsafe {
+            gl::GetSynciv(sync, pname, 1, &mut length, &mut value);
+        }
+        value
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_tex_parameterfv(target: GLenum, pname: GLenum, params: &mut [GLfloat]) {
-    unsafe { gl::GetTexParameterfv(target, pname, params.as_mut_ptr()) }
+    checked!(get_tex_parameterfv, [target, pname, params], {
+        unsafe { gl::GetTexParameterfv(target, pname, params.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_tex_parameteriv(target: GLenum, pname: GLenum, params: &mut [GLint]) {
-    unsafe { gl::GetTexParameteriv(target, pname, params.as_mut_ptr()) }
+    checked!(get_tex_parameteriv, [target, pname, params], {
+        unsafe { gl::GetTexParameteriv(target, pname, params.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_uniform_location<S: AsRef<str>>(program: GLuint, name: S) -> Result<GLint, Error> {
-    let name = CString::new(name.as_ref()).unwrap();
-    match unsafe { gl::GetUniformLocation(program, name.as_ptr()) } {
-        -1 => Err(Error::new()),
-        other => Ok(other),
-    }
+    checked!(get_uniform_location, [program], {
+        let name = CString::new(name.as_ref()).unwrap();
+        match unsafe { gl::GetUniformLocation(program, name.as_ptr()) } {
+            -1 => Err(Error::new()),
+            other => Ok(other),
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_uniformfv(program: GLuint, location: GLint, params: &mut [GLfloat]) {
-    unsafe { gl::GetUniformfv(program, location, params.as_mut_ptr()) }
+    checked!(get_uniformfv, [program, location, params], {
+        unsafe { gl::GetUniformfv(program, location, params.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn get_uniformiv(program: GLuint, location: GLint, params: &mut [GLint]) {
-    unsafe { gl::GetUniformiv(program, location, params.as_mut_ptr()) }
+    checked!(get_uniformiv, [program, location, params], {
+        unsafe { gl::GetUniformiv(program, location, params.as_mut_ptr()) }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn hint(target: GLenum, mode: GLenum) {
-    unsafe {
-        gl::Hint(target, mode);
-    }
+    checked!(hint, [target, mode], {
+        unsafe {
+            gl::Hint(target, mode);
+        }
+    })
+}
+
+/// Test whether a capability is enabled.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn is_enabled(cap: GLenum) -> bool {
+    checked!(is_enabled, [cap], {
+        unsafe { gl::IsEnabled(cap) == gl::TRUE }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn line_width(width: GLfloat) {
-    unsafe {
-        gl::LineWidth(width);
-    }
+    checked!(line_width, [width], {
+        unsafe {
+            gl::LineWidth(width);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn link_program(program: GLuint) {
-    unsafe {
-        gl::LinkProgram(program);
-    }
+    checked!(link_program, [program], {
+        unsafe {
+            gl::LinkProgram(program);
+        }
+    })
+}
+
+/// Label the object `name` of the `identifier` namespace, like `GL_BUFFER`
+/// or `GL_TEXTURE`, for the debug messages and the debuggers.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn object_label(identifier: GLenum, name: GLuint, label: &str) {
+    checked!(object_label, [identifier, name, label], {
+        unsafe {
+            gl::ObjectLabel(
+                identifier,
+                name,
+                label.len() as GLsizei,
+                label.as_ptr() as *const GLchar,
+            )
+        }
+    })
 }
 
-/// Replaces the source code in a shader object.
+/// Pause transform feedback operations.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn pause_transform_feedback() {
+    checked!(pause_transform_feedback, [], {
+        unsafe { gl::PauseTransformFeedback() }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn pop_debug_group() {
+    checked!(pop_debug_group, [], { unsafe { gl::PopDebugGroup() } })
+}
+
+/// Set the scale and units used to calculate depth values.
 ///
 /// # Parameters
 ///
-/// * `shader` - Specifies the handle of the shader object whose source code is to be replaced.
-/// * `source` - Specifies source code to be loaded into the shader.
-pub fn shader_source<S: AsRef<str>>(shader: GLuint, source: S) {
-    let src = CString::new(source.as_ref()).unwrap();
-    let string = src.as_ptr();
-    unsafe {
-        gl::ShaderSource(shader, 1, &string as *const *const GLchar, std::ptr::null());
-    }
+/// * `factor` - Specifies a scale factor that is used to create a variable depth offset for each polygon.
+/// * `units` - Is multiplied by an implementation-specific value to create a constant depth offset.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn polygon_offset(factor: GLfloat, units: GLfloat) {
+    checked!(polygon_offset, [factor, units], {
+        unsafe { gl::PolygonOffset(factor, units) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn push_debug_group(source: GLenum, id: GLuint, message: &str) {
+    checked!(push_debug_group, [source, id, message], {
+        unsafe {
+            gl::PushDebugGroup(
+                source,
+                id,
+                message.len() as GLsizei,
+                message.as_ptr() as *const GLchar,
+            )
+        }
+    })
 }
 
-pub fn scissor(x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
-    unsafe { gl::Scissor(x, y, width, height) }
+/// Set pixel storage modes.
+///
+/// # Parameters
+///
+/// * `pname` - Specifies the symbolic name of the parameter to be set, such as GL_UNPACK_ALIGNMENT or GL_UNPACK_ROW_LENGTH.
+/// * `param` - Specifies the value that pname is set to.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn pixel_storei(pname: GLenum, param: GLint) {
+    checked!(pixel_storei, [pname, param], {
+        unsafe { gl::PixelStorei(pname, param) }
+    })
 }
 
-pub fn tex_image2d<T>(
-    target: GLenum,
-    level: GLint,
-    internalformat: GLint,
-    width: GLsizei,
-    height: GLsizei,
-    border: GLint,
-    format: GLenum,
+/// Select a color buffer source for pixels.
+///
+/// # Parameters
+///
+/// * `src` - Specifies a color buffer, GL_BACK, GL_NONE or GL_COLOR_ATTACHMENTi.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn read_buffer(src: GLenum) {
+    checked!(read_buffer, [src], { unsafe { gl::ReadBuffer(src) } })
+}
+
+/// Record the GPU time into a query object after all previous commands have completed.
+///
+/// # Parameters
+///
+/// * `id` - Specify the name of a query object into which to record the GPU time.
+/// * `target` - Specify the counter to query, must be GL_TIMESTAMP.
+#[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn query_counter(id: GLuint, target: GLenum) {
+    checked!(query_counter, [id, target], {
+        unsafe { gl::QueryCounterEXT(id, target) }
+    })
+}
+
+/// Record the GPU time into a query object after all previous commands have completed.
+#[cfg(not(any(feature = "gles1", feature = "gles2", feature = "gles3")))]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn query_counter(id: GLuint, target: GLenum) {
+    checked!(query_counter, [id, target], {
+        unsafe { gl::QueryCounter(id, target) }
+    })
+}
+
+/// Read a block of pixels from the frame buffer.
+///
+/// # Parameters
+///
+/// * `x`, `y` - Specify the window coordinates of the first pixel that is read from the frame buffer.
+/// * `width`, `height` - Specify the dimensions of the pixel rectangle.
+/// * `format` - Specifies the format of the pixel data.
+/// * `type_` - Specifies the data type of the pixel data.
+/// * `pixels` - Returns the pixel data.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn read_pixels<T>(
+    x: GLint,
+    y: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    format: GLenum,
     type_: GLenum,
-    pixels: Option<&[T]>,
+    pixels: &mut [T],
 ) where
     T: Sized,
 {
-    match pixels {
-        Some(v) => unsafe {
-            gl::TexImage2D(
-                target,
-                level,
-                internalformat,
+    checked!(read_pixels, [x, y, width, height, format, type_], {
+        unsafe {
+            gl::ReadPixels(
+                x,
+                y,
                 width,
                 height,
-                border,
                 format,
                 type_,
-                v.as_ptr() as *const core::ffi::c_void,
-            );
-        },
-        None => unsafe {
-            gl::TexImage2D(
-                target,
-                level,
-                internalformat,
-                width,
-                height,
-                border,
-                format,
-                type_,
-                std::ptr::null(),
-            );
-        },
-    }
+                pixels.as_mut_ptr() as *mut c_void,
+            )
+        }
+    })
+}
+
+/// Read a block of pixels into the binded GL_PIXEL_PACK_BUFFER at `offset`.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn read_pixels_offset(
+    x: GLint,
+    y: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    format: GLenum,
+    type_: GLenum,
+    offset: GLintptr,
+) {
+    checked!(
+        read_pixels_offset,
+        [x, y, width, height, format, type_, offset],
+        { unsafe { gl::ReadPixels(x, y, width, height, format, type_, offset as *mut c_void) } }
+    )
 }
 
+/// Resume transform feedback operations.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn resume_transform_feedback() {
+    checked!(resume_transform_feedback, [], {
+        unsafe { gl::ResumeTransformFeedback() }
+    })
+}
+
+/// Establish data storage, format and dimensions of a renderbuffer object's image.
+///
+/// # Parameters
+///
+/// * `target` - Specifies the renderbuffer target, must be GL_RENDERBUFFER.
+/// * `internalformat` - Specifies the sized internal format of the renderbuffer.
+/// * `width` - Specifies the width of the renderbuffer, in pixels.
+/// * `height` - Specifies the height of the renderbuffer, in pixels.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn renderbuffer_storage(
+    target: GLenum,
+    internalformat: GLenum,
+    width: GLsizei,
+    height: GLsizei,
+) {
+    checked!(
+        renderbuffer_storage,
+        [target, internalformat, width, height],
+        { unsafe { gl::RenderbufferStorage(target, internalformat, width, height) } }
+    )
+}
+
+/// Establish data storage, format, dimensions and sample count of a renderbuffer object's image.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn renderbuffer_storage_multisample(
+    target: GLenum,
+    samples: GLsizei,
+    internalformat: GLenum,
+    width: GLsizei,
+    height: GLsizei,
+) {
+    checked!(
+        renderbuffer_storage_multisample,
+        [target, samples, internalformat, width, height],
+        {
+            unsafe {
+                gl::RenderbufferStorageMultisample(target, samples, internalformat, width, height)
+            }
+        }
+    )
+}
+
+/// Set sampler parameters.
+///
+/// # Parameters
+///
+/// * `sampler` - Specifies the sampler object whose parameter to modify.
+/// * `pname` - Specifies the symbolic name of a sampler parameter.
+/// * `param` - Specifies the value of pname.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn sampler_parameterf(sampler: GLuint, pname: GLenum, param: GLfloat) {
+    checked!(sampler_parameterf, [sampler, pname, param], {
+        unsafe { gl::SamplerParameterf(sampler, pname, param) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn sampler_parameteri(sampler: GLuint, pname: GLenum, param: GLint) {
+    checked!(sampler_parameteri, [sampler, pname, param], {
+        unsafe { gl::SamplerParameteri(sampler, pname, param) }
+    })
+}
+
+/// Replaces the source code in a shader object.
+///
+/// # Parameters
+///
+/// * `shader` - Specifies the handle of the shader object whose source code is to be replaced.
+/// * `source` - Specifies source code to be loaded into the shader.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn shader_source<S: AsRef<str>>(shader: GLuint, source: S) {
+    checked!(shader_source, [shader], {
+        let src = CString::new(source.as_ref()).unwrap();
+        let string = src.as_ptr();
+        unsafe {
+            gl::ShaderSource(shader, 1, &string as *const *const GLchar, std::ptr::null());
+        }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn scissor(x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
+    checked!(scissor, [x, y, width, height], {
+        unsafe { gl::Scissor(x, y, width, height) }
+    })
+}
+
+/// Set front and/or back function and reference value for stencil testing.
+///
+/// # Parameters
+///
+/// * `face` - Specifies whether front and/or back stencil state is updated.
+/// * `func` - Specifies the test function.
+/// * `ref_` - Specifies the reference value for the stencil test.
+/// * `mask` - Specifies a mask that is ANDed with both the reference value and the stored stencil value when the test is done.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn stencil_func_separate(face: GLenum, func: GLenum, ref_: GLint, mask: GLuint) {
+    checked!(stencil_func_separate, [face, func, ref_, mask], {
+        unsafe { gl::StencilFuncSeparate(face, func, ref_, mask) }
+    })
+}
+
+/// Control the front and/or back writing of individual bits in the stencil planes.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn stencil_mask_separate(face: GLenum, mask: GLuint) {
+    checked!(stencil_mask_separate, [face, mask], {
+        unsafe { gl::StencilMaskSeparate(face, mask) }
+    })
+}
+
+/// Set front and/or back stencil test actions.
+///
+/// # Parameters
+///
+/// * `face` - Specifies whether front and/or back stencil state is updated.
+/// * `sfail` - Specifies the action to take when the stencil test fails.
+/// * `dpfail` - Specifies the stencil action when the stencil test passes, but the depth test fails.
+/// * `dppass` - Specifies the stencil action when both the stencil test and the depth test pass.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn stencil_op_separate(face: GLenum, sfail: GLenum, dpfail: GLenum, dppass: GLenum) {
+    checked!(stencil_op_separate, [face, sfail, dpfail, dppass], {
+        unsafe { gl::StencilOpSeparate(face, sfail, dpfail, dppass) }
+    })
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn tex_image2d<T>(
+    target: GLenum,
+    level: GLint,
+    internalformat: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    border: GLint,
+    format: GLenum,
+    type_: GLenum,
+    pixels: Option<&[T]>,
+) where
+    T: Sized,
+{
+    checked!(
+        tex_image2d,
+        [
+            target,
+            level,
+            internalformat,
+            width,
+            height,
+            border,
+            format,
+            type_
+        ],
+        {
+            match pixels {
+                Some(v) => unsafe {
+                    gl::TexImage2D(
+                        target,
+                        level,
+                        internalformat,
+                        width,
+                        height,
+                        border,
+                        format,
+                        type_,
+                        v.as_ptr() as *const core::ffi::c_void,
+                    );
+                },
+                None => unsafe {
+                    gl::TexImage2D(
+                        target,
+                        level,
+                        internalformat,
+                        width,
+                        height,
+                        border,
+                        format,
+                        type_,
+                        std::ptr::null(),
+                    );
+                },
+            }
+        }
+    )
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn tex_image3d<T>(
     target: GLenum,
     level: GLint,
@@ -742,63 +1838,92 @@ pub fn tex_image3d<T>(
 ) where
     T: Sized,
 {
-    match pixels {
-        Some(v) => unsafe {
-            gl::TexImage3D(
-                target,
-                level,
-                internalformat,
-                width,
-                height,
-                depth,
-                border,
-                format,
-                type_,
-                v.as_ptr() as *const core::ffi::c_void,
-            );
-        },
-        None => unsafe {
-            gl::TexImage3D(
-                target,
-                level,
-                internalformat,
-                width,
-                height,
-                depth,
-                border,
-                format,
-                type_,
-                std::ptr::null(),
-            );
-        },
-    }
+    checked!(
+        tex_image3d,
+        [
+            target,
+            level,
+            internalformat,
+            width,
+            height,
+            depth,
+            border,
+            format,
+            type_
+        ],
+        {
+            match pixels {
+                Some(v) => unsafe {
+                    gl::TexImage3D(
+                        target,
+                        level,
+                        internalformat,
+                        width,
+                        height,
+                        depth,
+                        border,
+                        format,
+                        type_,
+                        v.as_ptr() as *const core::ffi::c_void,
+                    );
+                },
+                None => unsafe {
+                    gl::TexImage3D(
+                        target,
+                        level,
+                        internalformat,
+                        width,
+                        height,
+                        depth,
+                        border,
+                        format,
+                        type_,
+                        std::ptr::null(),
+                    );
+                },
+            }
+        }
+    )
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn tex_parameterf(target: GLenum, pname: GLenum, param: GLfloat) {
-    unsafe {
-        gl::TexParameterf(target, pname, param);
-    }
+    checked!(tex_parameterf, [target, pname, param], {
+        unsafe {
+            gl::TexParameterf(target, pname, param);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn tex_parameterfv(target: GLenum, pname: GLenum, params: &[GLfloat]) {
-    unsafe {
-        gl::TexParameterfv(target, pname, params.as_ptr());
-    }
+    checked!(tex_parameterfv, [target, pname, params], {
+        unsafe {
+            gl::TexParameterfv(target, pname, params.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn tex_parameteri(target: GLenum, pname: GLenum, param: GLint) {
-    unsafe {
-        gl::TexParameteri(target, pname, param);
-    }
+    checked!(tex_parameteri, [target, pname, param], {
+        unsafe {
+            gl::TexParameteri(target, pname, param);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn tex_parameteriv(target: GLenum, pname: GLenum, params: &[GLint]) {
-    unsafe {
-        gl::TexParameteriv(target, pname, params.as_ptr());
-    }
+    checked!(tex_parameteriv, [target, pname, params], {
+        unsafe {
+            gl::TexParameteriv(target, pname, params.as_ptr());
+        }
+    })
 }
 
 /// Specify a two-dimensional texture subimage.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn tex_sub_image2d<T>(
     target: GLenum,
     level: GLint,
@@ -812,21 +1937,113 @@ pub fn tex_sub_image2d<T>(
 ) where
     T: Sized,
 {
-    unsafe {
-        gl::TexSubImage2D(
-            target,
-            level,
-            xoffset,
-            yoffset,
-            width,
-            height,
-            format,
-            type_,
-            pixels.as_ptr() as *const core::ffi::c_void,
-        );
-    }
+    checked!(
+        tex_sub_image2d,
+        [target, level, xoffset, yoffset, width, height, format, type_],
+        {
+            unsafe {
+                gl::TexSubImage2D(
+                    target,
+                    level,
+                    xoffset,
+                    yoffset,
+                    width,
+                    height,
+                    format,
+                    type_,
+                    pixels.as_ptr() as *const core::ffi::c_void,
+                );
+            }
+        }
+    )
+}
+
+/// Specify a two-dimensional texture subimage from the binded GL_PIXEL_UNPACK_BUFFER at `offset`.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn tex_sub_image2d_offset(
+    target: GLenum,
+    level: GLint,
+    xoffset: GLint,
+    yoffset: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    format: GLenum,
+    type_: GLenum,
+    offset: GLintptr,
+) {
+    checked!(
+        tex_sub_image2d_offset,
+        [target, level, xoffset, yoffset, width, height, format, type_, offset],
+        {
+            unsafe {
+                gl::TexSubImage2D(
+                    target,
+                    level,
+                    xoffset,
+                    yoffset,
+                    width,
+                    height,
+                    format,
+                    type_,
+                    offset as *const c_void,
+                );
+            }
+        }
+    )
 }
 
+/// Simultaneously specify storage for all levels of a two-dimensional or one-dimensional array texture.
+///
+/// # Parameters
+///
+/// * `target` - Specifies the target to which the texture object is bound.
+/// * `levels` - Specify the number of texture levels.
+/// * `internalformat` - Specifies the sized internal format to be used to store texture image data.
+/// * `width` - Specifies the width of the texture, in texels.
+/// * `height` - Specifies the height of the texture, in texels.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn tex_storage2d(
+    target: GLenum,
+    levels: GLsizei,
+    internalformat: GLenum,
+    width: GLsizei,
+    height: GLsizei,
+) {
+    checked!(
+        tex_storage2d,
+        [target, levels, internalformat, width, height],
+        { unsafe { gl::TexStorage2D(target, levels, internalformat, width, height) } }
+    )
+}
+
+/// Simultaneously specify storage for all levels of a three-dimensional, two-dimensional array or cube-map array texture.
+///
+/// # Parameters
+///
+/// * `target` - Specifies the target to which the texture object is bound.
+/// * `levels` - Specify the number of texture levels.
+/// * `internalformat` - Specifies the sized internal format to be used to store texture image data.
+/// * `width` - Specifies the width of the texture, in texels.
+/// * `height` - Specifies the height of the texture, in texels.
+/// * `depth` - Specifies the depth of the texture, in texels.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn tex_storage3d(
+    target: GLenum,
+    levels: GLsizei,
+    internalformat: GLenum,
+    width: GLsizei,
+    height: GLsizei,
+    depth: GLsizei,
+) {
+    checked!(
+        tex_storage3d,
+        [target, levels, internalformat, width, height, depth],
+        { unsafe { gl::TexStorage3D(target, levels, internalformat, width, height, depth) } }
+    )
+}
+
+/// Specify a three-dimensional texture subimage.
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn tex_sub_image3d<T>(
     target: GLenum,
     level: GLint,
@@ -842,275 +2059,448 @@ pub fn tex_sub_image3d<T>(
 ) where
     T: Sized,
 {
-    unsafe {
-        gl::TexSubImage3D(
-            target,
-            level,
-            xoffset,
-            yoffset,
-            zoffset,
-            width,
-            height,
-            depth,
-            format,
-            type_,
-            pixels.as_ptr() as *const core::ffi::c_void,
-        );
-    }
+    checked!(
+        tex_sub_image3d,
+        [target, level, xoffset, yoffset, zoffset, width, height, depth, format, type_],
+        {
+            unsafe {
+                gl::TexSubImage3D(
+                    target,
+                    level,
+                    xoffset,
+                    yoffset,
+                    zoffset,
+                    width,
+                    height,
+                    depth,
+                    format,
+                    type_,
+                    pixels.as_ptr() as *const core::ffi::c_void,
+                );
+            }
+        }
+    )
 }
 
+/// Specify values to record in transform feedback buffers.
+///
+/// # Parameters
+///
+/// * `program` - The name of the target program object.
+/// * `varyings` - The names of the varying variables to use for transform feedback.
+/// * `buffer_mode` - Identifies the mode used to capture the varying variables, GL_INTERLEAVED_ATTRIBS or GL_SEPARATE_ATTRIBS.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn transform_feedback_varyings<T: AsRef<str>>(
+    program: GLuint,
+    varyings: &[T],
+    buffer_mode: GLenum,
+) {
+    checked!(transform_feedback_varyings, [program, buffer_mode], {
+        let cv = varying_names(varyings);
+        let pv: Vec<*const GLchar> = cv.iter().map(|s| s.as_ptr()).collect();
+        unsafe {
+            gl::TransformFeedbackVaryings(program, pv.len() as GLsizei, pv.as_ptr(), buffer_mode)
+        }
+    })
+}
+
+/// Returns the NUL terminated names of the transform feedback `varyings`, in order.
+fn varying_names<T: AsRef<str>>(varyings: &[T]) -> Vec<CString> {
+    varyings
+        .iter()
+        .map(|s| CString::new(s.as_ref()).unwrap())
+        .collect()
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform1f(location: GLint, v0: GLfloat) {
-    unsafe {
-        gl::Uniform1f(location, v0);
-    }
+    checked!(uniform1f, [location, v0], {
+        unsafe {
+            gl::Uniform1f(location, v0);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform1fv(location: GLint, va: &[GLfloat]) {
-    unsafe {
-        gl::Uniform1fv(location, va.len() as GLsizei, va.as_ptr());
-    }
+    checked!(uniform1fv, [location, va], {
+        unsafe {
+            gl::Uniform1fv(location, va.len() as GLsizei, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform1i(location: GLint, v0: GLint) {
-    unsafe {
-        gl::Uniform1i(location, v0);
-    }
+    checked!(uniform1i, [location, v0], {
+        unsafe {
+            gl::Uniform1i(location, v0);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform1iv(location: GLint, va: &[GLint]) {
-    unsafe {
-        gl::Uniform1iv(location, va.len() as GLsizei, va.as_ptr());
-    }
+    checked!(uniform1iv, [location, va], {
+        unsafe {
+            gl::Uniform1iv(location, va.len() as GLsizei, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform1ui(location: GLint, v0: GLuint) {
-    unsafe {
-        gl::Uniform1ui(location, v0);
-    }
+    checked!(uniform1ui, [location, v0], {
+        unsafe {
+            gl::Uniform1ui(location, v0);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform1uiv(location: GLint, va: &[GLuint]) {
-    unsafe {
-        gl::Uniform1uiv(location, va.len() as GLsizei, va.as_ptr());
-    }
+    checked!(uniform1uiv, [location, va], {
+        unsafe {
+            gl::Uniform1uiv(location, va.len() as GLsizei, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform2f(location: GLint, v0: GLfloat, v1: GLfloat) {
-    unsafe {
-        gl::Uniform2f(location, v0, v1);
-    }
+    checked!(uniform2f, [location, v0, v1], {
+        unsafe {
+            gl::Uniform2f(location, v0, v1);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform2fv(location: GLint, va: &[[GLfloat; 2usize]]) {
-    unsafe {
-        gl::Uniform2fv(location, va.len() as GLsizei, va.as_ptr() as *const GLfloat);
-    }
+    checked!(uniform2fv, [location, va], {
+        unsafe {
+            gl::Uniform2fv(location, va.len() as GLsizei, va.as_ptr() as *const GLfloat);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform2i(location: GLint, v0: GLint, v1: GLint) {
-    unsafe {
-        gl::Uniform2i(location, v0, v1);
-    }
+    checked!(uniform2i, [location, v0, v1], {
+        unsafe {
+            gl::Uniform2i(location, v0, v1);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform2iv(location: GLint, va: &[[GLint; 2usize]]) {
-    unsafe {
-        gl::Uniform2iv(location, va.len() as GLsizei, va.as_ptr() as *const GLint);
-    }
+    checked!(uniform2iv, [location, va], {
+        unsafe {
+            gl::Uniform2iv(location, va.len() as GLsizei, va.as_ptr() as *const GLint);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform2ui(location: GLint, v0: GLuint, v1: GLuint) {
-    unsafe {
-        gl::Uniform2ui(location, v0, v1);
-    }
+    checked!(uniform2ui, [location, v0, v1], {
+        unsafe {
+            gl::Uniform2ui(location, v0, v1);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform2uiv(location: GLint, va: &[[GLuint; 2usize]]) {
-    unsafe {
-        gl::Uniform2uiv(location, va.len() as GLsizei, va.as_ptr() as *const GLuint);
-    }
+    checked!(uniform2uiv, [location, va], {
+        unsafe {
+            gl::Uniform2uiv(location, va.len() as GLsizei, va.as_ptr() as *const GLuint);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform3f(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat) {
-    unsafe {
-        gl::Uniform3f(location, v0, v1, v2);
-    }
+    checked!(uniform3f, [location, v0, v1, v2], {
+        unsafe {
+            gl::Uniform3f(location, v0, v1, v2);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform3fv(location: GLint, va: &[[GLfloat; 3usize]]) {
-    unsafe {
-        gl::Uniform3fv(location, va.len() as GLsizei, va.as_ptr() as *const GLfloat);
-    }
+    checked!(uniform3fv, [location, va], {
+        unsafe {
+            gl::Uniform3fv(location, va.len() as GLsizei, va.as_ptr() as *const GLfloat);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform3i(location: GLint, v0: GLint, v1: GLint, v2: GLint) {
-    unsafe {
-        gl::Uniform3i(location, v0, v1, v2);
-    }
+    checked!(uniform3i, [location, v0, v1, v2], {
+        unsafe {
+            gl::Uniform3i(location, v0, v1, v2);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform3iv(location: GLint, va: &[[GLint; 3usize]]) {
-    unsafe {
-        gl::Uniform3iv(location, va.len() as GLsizei, va.as_ptr() as *const GLint);
-    }
+    checked!(uniform3iv, [location, va], {
+        unsafe {
+            gl::Uniform3iv(location, va.len() as GLsizei, va.as_ptr() as *const GLint);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform3ui(location: GLint, v0: GLuint, v1: GLuint, v2: GLuint) {
-    unsafe {
-        gl::Uniform3ui(location, v0, v1, v2);
-    }
+    checked!(uniform3ui, [location, v0, v1, v2], {
+        unsafe {
+            gl::Uniform3ui(location, v0, v1, v2);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform3uiv(location: GLint, va: &[[GLuint; 3usize]]) {
-    unsafe {
-        gl::Uniform3uiv(location, va.len() as GLsizei, va.as_ptr() as *const GLuint);
-    }
+    checked!(uniform3uiv, [location, va], {
+        unsafe {
+            gl::Uniform3uiv(location, va.len() as GLsizei, va.as_ptr() as *const GLuint);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform4f(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat, v3: GLfloat) {
-    unsafe {
-        gl::Uniform4f(location, v0, v1, v2, v3);
-    }
+    checked!(uniform4f, [location, v0, v1, v2, v3], {
+        unsafe {
+            gl::Uniform4f(location, v0, v1, v2, v3);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform4fv(location: GLint, va: &[[GLfloat; 4usize]]) {
-    unsafe {
-        gl::Uniform4fv(location, va.len() as GLsizei, va.as_ptr() as *const GLfloat);
-    }
+    checked!(uniform4fv, [location, va], {
+        unsafe {
+            gl::Uniform4fv(location, va.len() as GLsizei, va.as_ptr() as *const GLfloat);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform4i(location: GLint, v0: GLint, v1: GLint, v2: GLint, v3: GLint) {
-    unsafe {
-        gl::Uniform4i(location, v0, v1, v2, v3);
-    }
+    checked!(uniform4i, [location, v0, v1, v2, v3], {
+        unsafe {
+            gl::Uniform4i(location, v0, v1, v2, v3);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform4iv(location: GLint, va: &[[GLint; 4usize]]) {
-    unsafe {
-        gl::Uniform4iv(location, va.len() as GLsizei, va.as_ptr() as *const GLint);
-    }
+    checked!(uniform4iv, [location, va], {
+        unsafe {
+            gl::Uniform4iv(location, va.len() as GLsizei, va.as_ptr() as *const GLint);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform4ui(location: GLint, v0: GLuint, v1: GLuint, v2: GLuint, v3: GLuint) {
-    unsafe {
-        gl::Uniform4ui(location, v0, v1, v2, v3);
-    }
+    checked!(uniform4ui, [location, v0, v1, v2, v3], {
+        unsafe {
+            gl::Uniform4ui(location, v0, v1, v2, v3);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform4uiv(location: GLint, va: &[[GLuint; 4usize]]) {
-    unsafe {
-        gl::Uniform4uiv(location, va.len() as GLsizei, va.as_ptr() as *const GLuint);
-    }
+    checked!(uniform4uiv, [location, va], {
+        unsafe {
+            gl::Uniform4uiv(location, va.len() as GLsizei, va.as_ptr() as *const GLuint);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix2fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix2fv(location, (va.len() / 4) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix2fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix2fv(location, (va.len() / 4) as GLsizei, transpose, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix2x3fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix2x3fv(location, (va.len() / 6) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix2x3fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix2x3fv(location, (va.len() / 6) as GLsizei, transpose, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix2x4fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix2x4fv(location, (va.len() / 8) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix2x4fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix2x4fv(location, (va.len() / 8) as GLsizei, transpose, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix3fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix3fv(location, (va.len() / 9) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix3fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix3fv(location, (va.len() / 9) as GLsizei, transpose, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix3x2fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix3x2fv(location, (va.len() / 6) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix3x2fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix3x2fv(location, (va.len() / 6) as GLsizei, transpose, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix3x4fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix3x4fv(location, (va.len() / 12) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix3x4fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix3x4fv(location, (va.len() / 12) as GLsizei, transpose, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix4fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix4fv(location, (va.len() / 16) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix4fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix4fv(location, (va.len() / 16) as GLsizei, transpose, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix4x2fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix4x2fv(location, (va.len() / 8) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix4x2fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix4x2fv(location, (va.len() / 8) as GLsizei, transpose, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn uniform_matrix4x3fv(location: GLint, transpose: GLboolean, va: &[GLfloat]) {
-    unsafe {
-        gl::UniformMatrix4x3fv(location, (va.len() / 12) as GLsizei, transpose, va.as_ptr());
-    }
+    checked!(uniform_matrix4x3fv, [location, transpose, va], {
+        unsafe {
+            gl::UniformMatrix4x3fv(location, (va.len() / 12) as GLsizei, transpose, va.as_ptr());
+        }
+    })
+}
+
+/// Release the mapping of a buffer object's data store into the client's address space.
+///
+/// Returns `false` if the data store contents have become corrupt during the mapping.
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn unmap_buffer(target: GLenum) -> bool {
+    checked!(unmap_buffer, [target], {
+        unsafe { gl::UnmapBuffer(target) == gl::TRUE }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn use_program(program: GLuint) {
-    unsafe {
-        gl::UseProgram(program);
-    }
+    checked!(use_program, [program], {
+        unsafe {
+            gl::UseProgram(program);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib1f(index: GLuint, x: GLfloat) {
-    unsafe {
-        gl::VertexAttrib1f(index, x);
-    }
+    checked!(vertex_attrib1f, [index, x], {
+        unsafe {
+            gl::VertexAttrib1f(index, x);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib1fv(index: GLuint, va: &[GLfloat]) {
-    unsafe {
-        gl::VertexAttrib1fv(index, va.as_ptr());
-    }
+    checked!(vertex_attrib1fv, [index, va], {
+        unsafe {
+            gl::VertexAttrib1fv(index, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib2f(index: GLuint, x: GLfloat, y: GLfloat) {
-    unsafe {
-        gl::VertexAttrib2f(index, x, y);
-    }
+    checked!(vertex_attrib2f, [index, x, y], {
+        unsafe {
+            gl::VertexAttrib2f(index, x, y);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib2fv(index: GLuint, va: &[GLfloat]) {
-    unsafe {
-        gl::VertexAttrib2fv(index, va.as_ptr());
-    }
+    checked!(vertex_attrib2fv, [index, va], {
+        unsafe {
+            gl::VertexAttrib2fv(index, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib3f(index: GLuint, x: GLfloat, y: GLfloat, z: GLfloat) {
-    unsafe {
-        gl::VertexAttrib3f(index, x, y, z);
-    }
+    checked!(vertex_attrib3f, [index, x, y, z], {
+        unsafe {
+            gl::VertexAttrib3f(index, x, y, z);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib3fv(index: GLuint, va: &[GLfloat]) {
-    unsafe {
-        gl::VertexAttrib3fv(index, va.as_ptr());
-    }
+    checked!(vertex_attrib3fv, [index, va], {
+        unsafe {
+            gl::VertexAttrib3fv(index, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib4f(index: GLuint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat) {
-    unsafe {
-        gl::VertexAttrib4f(index, x, y, z, w);
-    }
+    checked!(vertex_attrib4f, [index, x, y, z, w], {
+        unsafe {
+            gl::VertexAttrib4f(index, x, y, z, w);
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib4fv(index: GLuint, va: &[GLfloat]) {
-    unsafe {
-        gl::VertexAttrib4fv(index, va.as_ptr());
-    }
+    checked!(vertex_attrib4fv, [index, va], {
+        unsafe {
+            gl::VertexAttrib4fv(index, va.as_ptr());
+        }
+    })
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn vertex_attrib_pointer(
     index: GLuint,
     size: GLint,
@@ -1119,22 +2509,46 @@ pub fn vertex_attrib_pointer(
     stride: GLsizei,
     pointer: GLsizeiptr,
 ) {
-    unsafe {
-        gl::VertexAttribPointer(
-            index,
-            size,
-            type_,
-            normalized,
-            stride,
-            pointer as *const c_void,
-        );
-    }
+    checked!(
+        vertex_attrib_pointer,
+        [index, size, type_, normalized, stride, pointer],
+        {
+            unsafe {
+                gl::VertexAttribPointer(
+                    index,
+                    size,
+                    type_,
+                    normalized,
+                    stride,
+                    pointer as *const c_void,
+                );
+            }
+        }
+    )
 }
 
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn viewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
-    unsafe {
-        gl::Viewport(x, y, width, height);
-    }
+    checked!(viewport, [x, y, width, height], {
+        unsafe {
+            gl::Viewport(x, y, width, height);
+        }
+    })
+}
+
+/// Instruct the GL server to block until the specified sync object becomes signaled.
+///
+/// # Parameters
+///
+/// * `sync` - Specifies the sync object whose status to wait on.
+/// * `flags` - A bitfield controlling the command flushing behavior, must be 0.
+/// * `timeout` - Specifies the timeout that the server should wait, must be GL_TIMEOUT_IGNORED.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn wait_sync(sync: GLsync, flags: GLbitfield, timeout: GLuint64) {
+    checked!(wait_sync, [sync, flags, timeout], {
+        unsafe { gl::WaitSync(sync, flags, timeout) }
+    })
 }
 
 #[cfg(test)]
@@ -1148,4 +2562,12 @@ mod tests {
         gl::load_with(|s| so.get_proc_address(s));
         assert_eq!(get_error(), gl::NO_ERROR);
     }
+
+    #[test]
+    fn test_varying_names() {
+        let names = varying_names(&["v_position", "v_velocity", "gl_NextBuffer"]);
+        let names: Vec<&str> = names.iter().map(|s| s.to_str().unwrap()).collect();
+        assert_eq!(names, vec!["v_position", "v_velocity", "gl_NextBuffer"]);
+        assert!(varying_names::<&str>(&[]).is_empty());
+    }
 }
@@ -1,18 +1,30 @@
-use crate::prelude::Bindable;
+use crate::{prelude::Bindable, StateCache};
 use std::fmt::Debug;
 use std::vec::Vec;
 
-#[derive(Clone, Default)]
+/// Binds a list of objects, and unbinds them in reverse order when dropped.
+///
+/// The bindings replaced by the list are saved by the StateCache, so nested
+/// binders restore the objects of the outer binder instead of binding `0`.
+/// The binder owns its scope, so it is not `Clone`.
 pub struct AutoBinder<'a> {
     list: Vec<&'a dyn Bindable>,
+    scope: usize,
 }
 
 impl<'a> AutoBinder<'a> {
     pub fn new(list: Vec<&'a dyn Bindable>) -> Self {
+        let scope = StateCache::with(|cache| cache.begin_scope());
         for a in list.iter() {
             a.bind();
         }
-        Self { list }
+        Self { list, scope }
+    }
+}
+
+impl<'a> Default for AutoBinder<'a> {
+    fn default() -> Self {
+        Self::new(Vec::new())
     }
 }
 
@@ -22,6 +34,7 @@ impl<'a> Drop for AutoBinder<'a> {
         for a in self.list.iter() {
             a.unbind();
         }
+        StateCache::with(|cache| cache.end_scope(self.scope));
     }
 }
 
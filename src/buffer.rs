@@ -1,4 +1,8 @@
-use crate::{gl, prelude::*, GLboolean, GLenum, GLint, GLsizei, GLsizeiptr, GLuint};
+use crate::{
+    gl, prelude::*, GLbitfield, GLboolean, GLenum, GLint, GLintptr, GLsizei, GLsizeiptr, GLuint,
+    StateCache,
+};
+use std::marker::PhantomData;
 
 #[derive(Clone, Default, Debug)]
 pub struct Buffer {
@@ -19,12 +23,36 @@ impl Buffer {
         Self::new(gl::DRAW_INDIRECT_BUFFER)
     }
 
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn new(buffer_type: GLuint) -> Buffer {
         let vbo = crate::new_buffer();
-        crate::bind_buffer(buffer_type, vbo);
+        StateCache::with(|cache| cache.bind_buffer(buffer_type, vbo));
         Buffer { buffer_type, vbo }
     }
 
+    /// Returns the Id of the buffer.
+    pub fn id(&self) -> GLuint {
+        self.vbo
+    }
+
+    /// Set the label of the buffer shown in the debug messages and the debuggers.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_label(&self, label: &str) {
+        crate::object_label(gl::BUFFER, self.id(), label);
+    }
+
+    /// Returns the label of the buffer, empty if not labeled.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn label(&self) -> String {
+        crate::get_object_label(gl::BUFFER, self.id())
+    }
+
+    /// Returns the target which the buffer binds to.
+    pub fn buffer_type(&self) -> GLenum {
+        self.buffer_type
+    }
+
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn static_draw_data<T>(&self, data: &[T])
     where
         T: Sized,
@@ -37,6 +65,7 @@ impl Buffer {
         );
     }
 
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn stream_draw_data<T>(&self, data: &[T])
     where
         T: Sized,
@@ -61,31 +90,68 @@ impl Buffer {
         );
     }
 
-    #[cfg(feature = "gl4")]
-    pub unsafe fn map_buffer_range_write_invalidate<'r, T>(
+    /// Map `size` elements of the buffer from `offset` elements with `access`.
+    ///
+    /// The buffer is binded to its target, the returned guard unmaps the buffer
+    /// when dropped.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn map_range<T>(
         &self,
         offset: usize,
         size: usize,
-    ) -> Option<MappedBuffer<'r, T>>
+        access: MapAccess,
+    ) -> Result<MappedBuffer<'_, T>, String>
     where
-        T: Sized,
+        T: Pod,
     {
+        access.validate()?;
+        self.bind();
         let ptr = crate::map_buffer_range(
-            self.buffer_type,                                  // target
-            (offset * std::mem::size_of::<T>()) as GLsizeiptr, // offset
-            (size * std::mem::size_of::<T>()) as GLsizeiptr,   //  length
-            gl::MAP_WRITE_BIT | gl::MAP_INVALIDATE_RANGE_BIT,  // usage
+            self.buffer_type,
+            (offset * std::mem::size_of::<T>()) as GLintptr,
+            (size * std::mem::size_of::<T>()) as GLsizeiptr,
+            access.bits(),
         );
-        if ptr == ::std::ptr::null_mut() {
-            return None;
+        if ptr.is_null() {
+            return Err(format!(
+                "Map buffer range {}+{} failed: {}!",
+                offset,
+                size,
+                crate::Error::new()
+            ));
         }
-        return Some(MappedBuffer {
-            buffer_type: self.buffer_type,
-            data: ::std::slice::from_raw_parts_mut(ptr as *mut T, size),
+        if !(ptr as *mut T).is_aligned() {
+            crate::unmap_buffer(self.buffer_type);
+            return Err(format!(
+                "Mapped pointer {:p} is not aligned to {} bytes!",
+                ptr,
+                std::mem::align_of::<T>()
+            ));
+        }
+        Ok(MappedBuffer {
+            buffer: self,
+            ptr: ptr as *mut T,
+            len: size,
             position: 0,
-        });
+            access,
+            _marker: PhantomData,
+        })
     }
 
+    /// Map the range for writing, the previous contents of the range are discarded.
+    pub fn map_buffer_range_write_invalidate<T>(
+        &self,
+        offset: usize,
+        size: usize,
+    ) -> Option<MappedBuffer<'_, T>>
+    where
+        T: Pod,
+    {
+        self.map_range(offset, size, MapAccess::WRITE | MapAccess::INVALIDATE_RANGE)
+            .ok()
+    }
+
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn update<T>(&self, data: &[T])
     where
         T: Sized,
@@ -97,6 +163,7 @@ impl Buffer {
         );
     }
 
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn update_partial<T>(&self, offset: isize, data: &[T])
     where
         T: Sized,
@@ -111,61 +178,233 @@ impl Buffer {
 
 impl Drop for Buffer {
     fn drop(&mut self) {
+        StateCache::with(|cache| cache.forget_buffer(self.vbo));
         crate::delete_buffers(&[self.vbo]);
     }
 }
 
 impl Bindable for Buffer {
     fn bind(&self) {
-        crate::bind_buffer(self.buffer_type, self.vbo);
+        StateCache::with(|cache| cache.bind_buffer(self.buffer_type, self.vbo));
     }
 
     fn unbind(&self) {
-        crate::bind_buffer(self.buffer_type, 0);
+        StateCache::with(|cache| cache.unbind_buffer(self.buffer_type, self.vbo));
+    }
+}
+
+/// Access flags of the buffer mapping.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MapAccess(GLbitfield);
+
+impl MapAccess {
+    /// The mapping may be read.
+    pub const READ: MapAccess = MapAccess(gl::MAP_READ_BIT);
+    /// The mapping may be written.
+    pub const WRITE: MapAccess = MapAccess(gl::MAP_WRITE_BIT);
+    /// The previous contents of the range may be discarded.
+    pub const INVALIDATE_RANGE: MapAccess = MapAccess(gl::MAP_INVALIDATE_RANGE_BIT);
+    /// The previous contents of the whole buffer may be discarded.
+    pub const INVALIDATE_BUFFER: MapAccess = MapAccess(gl::MAP_INVALIDATE_BUFFER_BIT);
+    /// Modifications are flushed explicitly with `flush_range()`.
+    pub const FLUSH_EXPLICIT: MapAccess = MapAccess(gl::MAP_FLUSH_EXPLICIT_BIT);
+    /// No synchronization with the pending operations on the buffer.
+    pub const UNSYNCHRONIZED: MapAccess = MapAccess(gl::MAP_UNSYNCHRONIZED_BIT);
+
+    /// Returns the raw `GL_MAP_*_BIT` bits.
+    pub fn bits(self) -> GLbitfield {
+        self.0
+    }
+
+    /// Returns `true` if all flags of `other` are set.
+    pub fn contains(self, other: MapAccess) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Check the combination of the flags which is allowed by OpenGL.
+    pub fn validate(self) -> Result<(), String> {
+        if !self.contains(Self::READ) && !self.contains(Self::WRITE) {
+            return Err("Map access requires READ or WRITE!".to_string());
+        }
+        if self.contains(Self::READ)
+            && (self.contains(Self::INVALIDATE_RANGE)
+                || self.contains(Self::INVALIDATE_BUFFER)
+                || self.contains(Self::UNSYNCHRONIZED))
+        {
+            return Err("Map access READ conflicts with INVALIDATE or UNSYNCHRONIZED!".to_string());
+        }
+        if self.contains(Self::FLUSH_EXPLICIT) && !self.contains(Self::WRITE) {
+            return Err("Map access FLUSH_EXPLICIT requires WRITE!".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::BitOr for MapAccess {
+    type Output = MapAccess;
+
+    fn bitor(self, rhs: MapAccess) -> MapAccess {
+        MapAccess(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for MapAccess {
+    fn bitor_assign(&mut self, rhs: MapAccess) {
+        self.0 |= rhs.0;
     }
 }
 
-#[cfg(feature = "gl4")]
+/// Plain old data which may be read from and written to the mapped buffers.
+///
+/// # Safety
+/// The type must be `Copy`, must not contain padding, pointers or references,
+/// and every bit pattern must be a valid value of the type.
+pub unsafe trait Pod: Copy + 'static {}
+
+unsafe impl Pod for u8 {}
+unsafe impl Pod for i8 {}
+unsafe impl Pod for u16 {}
+unsafe impl Pod for i16 {}
+unsafe impl Pod for u32 {}
+unsafe impl Pod for i32 {}
+unsafe impl Pod for u64 {}
+unsafe impl Pod for i64 {}
+unsafe impl Pod for f32 {}
+unsafe impl Pod for f64 {}
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// Guard of the mapped range of a Buffer, unmaps the buffer when dropped.
+///
+/// The contents can only be read through a mapping with `MapAccess::READ`,
+/// write-only mappings may be uninitialized. An error of unmapping is only
+/// logged when the guard dropped, use `unmap()` to handle it.
+#[derive(Debug)]
 pub struct MappedBuffer<'a, DataT: 'a> {
-    buffer_type: GLuint,
-    data: &'a mut [DataT],
+    buffer: &'a Buffer,
+    ptr: *mut DataT,
+    len: usize,
     position: usize,
+    access: MapAccess,
+    _marker: PhantomData<&'a mut [DataT]>,
 }
 
-#[cfg(feature = "gl4")]
-impl<'a, DataT: 'a> MappedBuffer<'a, DataT> {
+impl<'a, DataT: Pod> MappedBuffer<'a, DataT> {
     pub fn clear(&mut self) {
         self.position = 0;
     }
 
+    /// Write `data` at the position after the previous pushes, does nothing
+    /// if the mapping is full or not writable.
     pub fn push(&mut self, data: DataT) {
-        if self.position < self.data.len() {
-            *unsafe { self.data.get_unchecked_mut(self.position) } = data;
+        if self.access.contains(MapAccess::WRITE) && self.position < self.len {
+            unsafe { self.ptr.add(self.position).write(data) };
             self.position += 1;
         }
     }
-}
 
-#[cfg(feature = "gl4")]
-impl<'a, DataT: 'a> ::std::ops::Deref for MappedBuffer<'a, DataT> {
-    type Target = [DataT];
+    /// Copy `data` into the mapped range from `offset` elements.
+    pub fn write(&mut self, offset: usize, data: &[DataT]) -> Result<(), String> {
+        if !self.access.contains(MapAccess::WRITE) {
+            return Err("Buffer is not mapped with WRITE!".to_string());
+        }
+        if offset + data.len() > self.len {
+            return Err(format!(
+                "Write range {}+{} out of mapped range {}!",
+                offset,
+                data.len(),
+                self.len
+            ));
+        }
+        unsafe {
+            std::ptr::copy_nonoverlapping(data.as_ptr(), self.ptr.add(offset), data.len());
+        }
+        Ok(())
+    }
+
+    /// Returns the contents of the mapped range, `None` if the buffer is not
+    /// mapped with `MapAccess::READ`.
+    pub fn as_slice(&self) -> Option<&[DataT]> {
+        if self.access.contains(MapAccess::READ) {
+            Some(unsafe { std::slice::from_raw_parts(self.ptr, self.len) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the mutable contents of the mapped range, `None` if the buffer
+    /// is not mapped with both `MapAccess::READ` and `MapAccess::WRITE`.
+    pub fn as_mut_slice(&mut self) -> Option<&mut [DataT]> {
+        if self.access.contains(MapAccess::READ | MapAccess::WRITE) {
+            Some(unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of the mapped elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
 
-    fn deref(&self) -> &Self::Target {
-        self.data
+    /// Returns `true` if no element mapped.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the access flags of the mapping.
+    pub fn access(&self) -> MapAccess {
+        self.access
+    }
+
+    /// Flush modifications of `size` elements from `offset` elements of the mapped range.
+    /// # Note
+    /// The buffer must be mapped with `MapAccess::FLUSH_EXPLICIT`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn flush_range(&self, offset: usize, size: usize) -> Result<(), String> {
+        if !self.access.contains(MapAccess::FLUSH_EXPLICIT) {
+            return Err("Buffer is not mapped with FLUSH_EXPLICIT!".to_string());
+        }
+        if offset + size > self.len {
+            return Err(format!(
+                "Flush range {}+{} out of mapped range {}!",
+                offset, size, self.len
+            ));
+        }
+        self.buffer.bind();
+        crate::flush_mapped_buffer_range(
+            self.buffer.buffer_type,
+            (offset * std::mem::size_of::<DataT>()) as GLintptr,
+            (size * std::mem::size_of::<DataT>()) as GLsizeiptr,
+        );
+        Ok(())
+    }
+
+    /// Unmap the buffer, returns error if the contents have become corrupted
+    /// during the mapping, e.g. the display mode changed.
+    pub fn unmap(self) -> Result<(), String> {
+        let okay = self.release();
+        std::mem::forget(self);
+        if okay {
+            Ok(())
+        } else {
+            Err("Buffer contents corrupted while mapped!".to_string())
+        }
     }
 }
 
-#[cfg(feature = "gl4")]
-impl<'a, DataT: 'a> ::std::ops::DerefMut for MappedBuffer<'a, DataT> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.data
+impl<'a, DataT: 'a> MappedBuffer<'a, DataT> {
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn release(&self) -> bool {
+        self.buffer.bind();
+        crate::unmap_buffer(self.buffer.buffer_type)
     }
 }
 
-#[cfg(feature = "gl4")]
 impl<'a, DataT: 'a> Drop for MappedBuffer<'a, DataT> {
     fn drop(&mut self) {
-        crate::unmap_buffer(self.buffer_type);
+        if !self.release() {
+            log::error!("Buffer contents corrupted while mapped, the data must be reinitialized!");
+        }
     }
 }
 
@@ -222,12 +461,14 @@ pub struct VertexArray {
 }
 
 impl VertexArray {
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn new() -> VertexArray {
         VertexArray {
             vao: crate::new_vertex_array(),
         }
     }
 
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn enable_attrib(&self, attr: &VertexAttrib) {
         crate::enable_vertex_attrib_array(attr.location);
         crate::vertex_attrib_pointer(
@@ -240,6 +481,7 @@ impl VertexArray {
         );
     }
 
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn disable_attrib(self, attr: &VertexAttrib) {
         crate::disable_vertex_attrib_array(attr.location);
     }
@@ -247,16 +489,41 @@ impl VertexArray {
 
 impl Drop for VertexArray {
     fn drop(&mut self) {
+        StateCache::with(|cache| cache.forget_vertex_array(self.vao));
         crate::delete_vertex_arrays(&[self.vao]);
     }
 }
 
 impl Bindable for VertexArray {
     fn bind(&self) {
-        crate::bind_vertex_array(self.vao);
+        StateCache::with(|cache| cache.bind_vertex_array(self.vao));
     }
 
     fn unbind(&self) {
-        crate::bind_vertex_array(0);
+        StateCache::with(|cache| cache.unbind_vertex_array(self.vao));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_access() {
+        let access = MapAccess::WRITE | MapAccess::INVALIDATE_RANGE;
+        assert!(access.contains(MapAccess::WRITE));
+        assert!(!access.contains(MapAccess::READ));
+        assert_eq!(
+            access.bits(),
+            gl::MAP_WRITE_BIT | gl::MAP_INVALIDATE_RANGE_BIT
+        );
+        assert!(access.validate().is_ok());
+        assert!(MapAccess::default().validate().is_err());
+        assert!((MapAccess::READ | MapAccess::UNSYNCHRONIZED)
+            .validate()
+            .is_err());
+        assert!((MapAccess::READ | MapAccess::FLUSH_EXPLICIT)
+            .validate()
+            .is_err());
     }
 }
@@ -1,43 +1,90 @@
-use crate::{gl, prelude::*, GLbitfield, GLfloat, GLint, Vector4};
+use crate::{gl, prelude::*, GLbitfield, GLfloat, GLint, GLuint, Scissor, Vector4};
 use std::option::Option;
 
+/// Number of the draw buffers which can be cleared separately.
+pub const MAX_CLEAR_DRAW_BUFFERS: usize = 8;
+
+/// Clear value of a draw buffer, the variant must match the format of the
+/// attachment: float for normalized and float formats, int or uint for the
+/// integer formats.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClearValue {
+    Float([GLfloat; 4]),
+    Int([GLint; 4]),
+    Uint([GLuint; 4]),
+}
+
+impl ClearValue {
+    /// Float color from `0xRRGGBBAA`.
+    pub fn from_hex(rgba: u32) -> Self {
+        ClearValue::Float(hex_to_rgba(rgba))
+    }
+}
+
+impl From<[GLfloat; 4]> for ClearValue {
+    fn from(value: [GLfloat; 4]) -> Self {
+        ClearValue::Float(value)
+    }
+}
+
+impl From<[GLint; 4]> for ClearValue {
+    fn from(value: [GLint; 4]) -> Self {
+        ClearValue::Int(value)
+    }
+}
+
+impl From<[GLuint; 4]> for ClearValue {
+    fn from(value: [GLuint; 4]) -> Self {
+        ClearValue::Uint(value)
+    }
+}
+
+impl From<Vector4> for ClearValue {
+    fn from(value: Vector4) -> Self {
+        ClearValue::Float([value.x, value.y, value.z, value.w])
+    }
+}
+
+/// Clears the buffers of the binded framebuffer on bind.
+///
+/// Like `glClear`, the clears honor the active scissor rectangle, so a
+/// ClearBuffers binded inside a `Scissor` only clears the clipped area.
 #[derive(Clone, Copy, Default, Debug)]
 pub struct ClearBuffers {
-    accum: Option<Vector4>,
     color: Option<Vector4>,
-    color_index: Option<GLfloat>,
     depth: Option<GLfloat>,
     stencil: Option<GLint>,
+    draw_buffers: [Option<ClearValue>; MAX_CLEAR_DRAW_BUFFERS],
+    scissor: Option<Scissor>,
 }
 
 impl ClearBuffers {
     pub fn new() -> Self {
         Self {
-            accum: None,
             color: None,
-            color_index: None,
             depth: None,
             stencil: None,
+            draw_buffers: [None; MAX_CLEAR_DRAW_BUFFERS],
+            scissor: None,
         }
     }
 
-    #[cfg(feature = "gl4")]
-    pub fn with_accum(mut self, accum: Option<Vector4>) -> Self {
-        self.accum = accum;
-        self
-    }
-
     pub fn with_color(mut self, color: Option<Vector4>) -> Self {
         self.color = color;
         self
     }
 
-    #[cfg(feature = "gl4")]
-    pub fn with_color_index(mut self, color_index: Option<GLfloat>) -> Self {
-        self.color_index = color_index;
+    /// Clear all color buffers to the `[r, g, b, a]` color.
+    pub fn with_color_rgba(mut self, rgba: [GLfloat; 4]) -> Self {
+        self.color = Some(Vector4::from(rgba));
         self
     }
 
+    /// Clear all color buffers to the `0xRRGGBBAA` color.
+    pub fn with_color_hex(self, rgba: u32) -> Self {
+        self.with_color_rgba(hex_to_rgba(rgba))
+    }
+
     pub fn with_depth(mut self, depth: Option<GLfloat>) -> Self {
         self.depth = depth;
         self
@@ -48,18 +95,26 @@ impl ClearBuffers {
         self
     }
 
-    #[cfg(feature = "gl4")]
-    pub fn set_accum(&mut self, accum: Vector4) {
-        self.accum = Some(accum);
+    /// Clear the draw buffer at `index` to its own `value`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `MAX_CLEAR_DRAW_BUFFERS`.
+    pub fn with_draw_buffer<V>(mut self, index: usize, value: V) -> Self
+    where
+        V: Into<ClearValue>,
+    {
+        self.set_draw_buffer(index, value);
+        self
     }
 
-    pub fn set_color(&mut self, color: Vector4) {
-        self.color = Some(color);
+    /// Clear only inside the `scissor` rectangle, intersected with the active one.
+    pub fn with_scissor(mut self, scissor: Scissor) -> Self {
+        self.scissor = Some(scissor);
+        self
     }
 
-    #[cfg(feature = "gl4")]
-    pub fn set_color_index(&mut self, color_index: GLfloat) {
-        self.color_index = Some(color_index);
+    pub fn set_color(&mut self, color: Vector4) {
+        self.color = Some(color);
     }
 
     pub fn set_depth(&mut self, depth: GLfloat) {
@@ -69,40 +124,100 @@ impl ClearBuffers {
     pub fn set_stencil(&mut self, stencil: GLint) {
         self.stencil = Some(stencil);
     }
-}
 
-impl Bindable for ClearBuffers {
-    fn bind(&self) {
+    /// Clear the draw buffer at `index` to its own `value`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `MAX_CLEAR_DRAW_BUFFERS`.
+    pub fn set_draw_buffer<V>(&mut self, index: usize, value: V)
+    where
+        V: Into<ClearValue>,
+    {
+        assert!(
+            index < MAX_CLEAR_DRAW_BUFFERS,
+            "Draw buffer index {} out of range!",
+            index
+        );
+        self.draw_buffers[index] = Some(value.into());
+    }
+
+    pub fn set_scissor(&mut self, scissor: Option<Scissor>) {
+        self.scissor = scissor;
+    }
+
+    fn clear(&self) {
         let mut mask: GLbitfield = 0;
-        #[cfg(feature = "gl4")]
-        {
-            if let Some(ref v) = self.accum {
-                crate::clear_accum(v);
-                mask |= gl::ACCUM_BUFFER_BIT;
-            }
-        }
         if let Some(v) = self.color {
             crate::clear_color(v.x, v.y, v.z, v.w);
             mask |= gl::COLOR_BUFFER_BIT;
         }
-        #[cfg(feature = "gl4")]
-        {
-            if let Some(v) = self.index {
-                crate::clear_index(v);
-                mask |= gl::COLOR_BUFFER_BIT;
+        match (self.depth, self.stencil) {
+            (Some(depth), Some(stencil)) => {
+                crate::clear_bufferfi(gl::DEPTH_STENCIL, 0, depth, stencil);
             }
-        }
-        if let Some(v) = self.depth {
-            crate::clear_depthf(v);
-            mask |= gl::DEPTH_BUFFER_BIT;
-        }
-        if let Some(v) = self.stencil {
-            crate::clear_stencil(v);
-            mask |= gl::STENCIL_BUFFER_BIT;
+            (Some(depth), None) => {
+                crate::clear_depthf(depth);
+                mask |= gl::DEPTH_BUFFER_BIT;
+            }
+            (None, Some(stencil)) => {
+                crate::clear_stencil(stencil);
+                mask |= gl::STENCIL_BUFFER_BIT;
+            }
+            (None, None) => {}
         }
         if mask != 0 {
             crate::clear(mask);
         }
+        for (index, value) in self.draw_buffers.iter().enumerate() {
+            let index = index as GLint;
+            match value {
+                Some(ClearValue::Float(v)) => crate::clear_bufferfv(gl::COLOR, index, v),
+                Some(ClearValue::Int(v)) => crate::clear_bufferiv(gl::COLOR, index, v),
+                Some(ClearValue::Uint(v)) => crate::clear_bufferuiv(gl::COLOR, index, v),
+                None => {}
+            }
+        }
+    }
+}
+
+impl Bindable for ClearBuffers {
+    fn bind(&self) {
+        match self.scissor {
+            Some(ref scissor) => {
+                scissor.bind();
+                self.clear();
+                scissor.unbind();
+            }
+            None => self.clear(),
+        }
     }
     fn unbind(&self) {}
 }
+
+/// Convert `0xRRGGBBAA` to normalized `[r, g, b, a]`.
+fn hex_to_rgba(rgba: u32) -> [GLfloat; 4] {
+    let channel = |shift: u32| ((rgba >> shift) & 0xff) as GLfloat / 255.0;
+    [channel(24), channel(16), channel(8), channel(0)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_color() {
+        assert_eq!(hex_to_rgba(0xff00_80ff), [1.0, 0.0, 128.0 / 255.0, 1.0]);
+        assert_eq!(
+            ClearValue::from_hex(0x0000_00ff),
+            ClearValue::Float([0.0, 0.0, 0.0, 1.0])
+        );
+        let clear = ClearBuffers::new()
+            .with_color_hex(0x3333_80ff)
+            .with_draw_buffer(1, [1u32, 2, 3, 4]);
+        assert_eq!(
+            clear.color,
+            Some(Vector4::new(0.2, 0.2, 128.0 / 255.0, 1.0))
+        );
+        assert_eq!(clear.draw_buffers[1], Some(ClearValue::Uint([1, 2, 3, 4])));
+    }
+}
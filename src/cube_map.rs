@@ -0,0 +1,244 @@
+use crate::{gl, prelude::*, Texture, TextureFormat, TextureTexel};
+
+/// Faces of the cube map texture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CubeMapFace {
+    PositiveX = gl::TEXTURE_CUBE_MAP_POSITIVE_X as isize,
+    NegativeX = gl::TEXTURE_CUBE_MAP_NEGATIVE_X as isize,
+    PositiveY = gl::TEXTURE_CUBE_MAP_POSITIVE_Y as isize,
+    NegativeY = gl::TEXTURE_CUBE_MAP_NEGATIVE_Y as isize,
+    PositiveZ = gl::TEXTURE_CUBE_MAP_POSITIVE_Z as isize,
+    NegativeZ = gl::TEXTURE_CUBE_MAP_NEGATIVE_Z as isize,
+}
+
+impl CubeMapFace {
+    /// All faces in the order of the OpenGL face targets.
+    pub const ALL: [CubeMapFace; 6] = [
+        CubeMapFace::PositiveX,
+        CubeMapFace::NegativeX,
+        CubeMapFace::PositiveY,
+        CubeMapFace::NegativeY,
+        CubeMapFace::PositiveZ,
+        CubeMapFace::NegativeZ,
+    ];
+}
+
+/// Arrangement of the six faces in a single image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CubeMapLayout {
+    /// 4x3 cells, `-X +Z +X -Z` on the middle row.
+    HorizontalCross,
+    /// 3x4 cells, `-X +Z +X` on the second row and `-Z` upside down at the bottom.
+    VerticalCross,
+    /// 6x1 cells ordered as `CubeMapFace::ALL`.
+    HorizontalStrip,
+    /// 1x6 cells ordered as `CubeMapFace::ALL`.
+    VerticalStrip,
+}
+
+impl CubeMapLayout {
+    /// Returns the number of columns and rows of the layout.
+    pub fn grid(self) -> (usize, usize) {
+        match self {
+            CubeMapLayout::HorizontalCross => (4, 3),
+            CubeMapLayout::VerticalCross => (3, 4),
+            CubeMapLayout::HorizontalStrip => (6, 1),
+            CubeMapLayout::VerticalStrip => (1, 6),
+        }
+    }
+
+    /// Returns the column and row of the `face` in the layout.
+    pub fn cell(self, face: CubeMapFace) -> (usize, usize) {
+        let index = CubeMapFace::ALL.iter().position(|f| *f == face).unwrap();
+        match self {
+            CubeMapLayout::HorizontalCross | CubeMapLayout::VerticalCross => match face {
+                CubeMapFace::PositiveX => (2, 1),
+                CubeMapFace::NegativeX => (0, 1),
+                CubeMapFace::PositiveY => (1, 0),
+                CubeMapFace::NegativeY => (1, 2),
+                CubeMapFace::PositiveZ => (1, 1),
+                CubeMapFace::NegativeZ => match self {
+                    CubeMapLayout::HorizontalCross => (3, 1),
+                    _ => (1, 3),
+                },
+            },
+            CubeMapLayout::HorizontalStrip => (index, 0),
+            CubeMapLayout::VerticalStrip => (0, index),
+        }
+    }
+
+    /// Returns the edge size of one face in an image of `width` x `height` pixels.
+    pub fn face_size(self, width: usize, height: usize) -> Result<usize, String> {
+        let (columns, rows) = self.grid();
+        let size = width / columns;
+        if size == 0 || width != size * columns || height != size * rows {
+            return Err(format!(
+                "Invalid {:?} cube map image size: {}x{}!",
+                self, width, height
+            ));
+        }
+        Ok(size)
+    }
+
+    /// Split the image into six faces ordered as `CubeMapFace::ALL`.
+    pub fn extract_faces(
+        self,
+        bytes: &[u8],
+        width: usize,
+        height: usize,
+        pixel_size: usize,
+    ) -> Result<Vec<Vec<u8>>, String> {
+        let size = self.face_size(width, height)?;
+        let stride = width * pixel_size;
+        let row_size = size * pixel_size;
+        if bytes.len() < stride * height {
+            return Err(format!(
+                "Cube map image requires {} bytes, but got {}!",
+                stride * height,
+                bytes.len()
+            ));
+        }
+        let faces = CubeMapFace::ALL
+            .iter()
+            .map(|face| {
+                let (column, row) = self.cell(*face);
+                let mut data = Vec::with_capacity(row_size * size);
+                for y in 0..size {
+                    let offset = (row * size + y) * stride + column * row_size;
+                    data.extend_from_slice(&bytes[offset..offset + row_size]);
+                }
+                if self == CubeMapLayout::VerticalCross && *face == CubeMapFace::NegativeZ {
+                    // The face is stored upside down, rotate it by 180 degrees.
+                    let mut rotated = Vec::with_capacity(data.len());
+                    for pixel in data.chunks(pixel_size).rev() {
+                        rotated.extend_from_slice(pixel);
+                    }
+                    data = rotated;
+                }
+                data
+            })
+            .collect();
+        Ok(faces)
+    }
+}
+
+/// Cube map texture for skyboxes and environment lighting.
+///
+/// Created by `TextureLoader::load_cube_map()`.
+#[derive(Debug)]
+pub struct CubeMapTexture<'a> {
+    texture: Texture<'a>,
+}
+
+impl<'a> CubeMapTexture<'a> {
+    /// Enable or disable seamless filtering across the cube map faces.
+    ///
+    /// # Note
+    /// This is a global state of the context which affects all cube maps,
+    /// `TextureLoader::load_cube_map()` enables it. OpenGL ES 3.0 always
+    /// filters seamlessly, so this is a no-op for GLES.
+    pub fn set_seamless(enabled: bool) {
+        #[cfg(any(feature = "gl3", feature = "gl4"))]
+        {
+            crate::StateCache::with(|cache| {
+                cache.set_enabled(gl::TEXTURE_CUBE_MAP_SEAMLESS, enabled)
+            });
+        }
+        #[cfg(not(any(feature = "gl3", feature = "gl4")))]
+        let _ = enabled;
+    }
+
+    /// Returns the edge size of the faces.
+    pub fn size(&self) -> usize {
+        self.texture.width()
+    }
+
+    /// Returns the underlying texture.
+    pub fn texture(&self) -> &Texture<'a> {
+        &self.texture
+    }
+
+    /// Update contents of the `face` at the mipmap `level`, levels above 0
+    /// upload precomputed mip chains.
+    ///
+    /// The `bytes` are read with the current pixel unpack parameters.
+    /// # Note
+    /// Must be binded before call the routine.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn update_face(
+        &self,
+        face: CubeMapFace,
+        level: usize,
+        format: TextureFormat,
+        texel: TextureTexel,
+        bytes: &[u8],
+    ) -> Result<(), String> {
+        self.texture.upload_face(face, level, format, texel, bytes)
+    }
+
+    /// Regenerate the mipmaps of all faces.
+    /// # Note
+    /// Must be binded before call the routine.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn generate_mipmap(&self) {
+        crate::generate_mipmap(gl::TEXTURE_CUBE_MAP);
+    }
+}
+
+impl<'a> From<Texture<'a>> for CubeMapTexture<'a> {
+    fn from(texture: Texture<'a>) -> Self {
+        Self { texture }
+    }
+}
+
+impl<'a> Bindable for CubeMapTexture<'a> {
+    fn bind(&self) {
+        self.texture.bind();
+    }
+
+    fn bind_at(&self, slot: u32) {
+        self.texture.bind_at(slot);
+    }
+
+    fn unbind(&self) {
+        self.texture.unbind();
+    }
+
+    fn unbind_at(&self, slot: u32) {
+        self.texture.unbind_at(slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_faces() {
+        // 4x3 cells of 1 pixel, each pixel holds its cell index.
+        let bytes: Vec<u8> = (0..12).collect();
+        let faces = CubeMapLayout::HorizontalCross
+            .extract_faces(&bytes, 4, 3, 1)
+            .unwrap();
+        assert_eq!(
+            faces,
+            vec![vec![6], vec![4], vec![1], vec![9], vec![5], vec![7]]
+        );
+        assert!(CubeMapLayout::HorizontalCross
+            .extract_faces(&bytes, 4, 4, 1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_extract_faces_rotated() {
+        // 3x4 cells of 2x2 pixels, -Z is stored upside down.
+        let mut bytes = vec![0u8; 6 * 8];
+        for (i, v) in [1u8, 2, 3, 4].iter().enumerate() {
+            bytes[(6 + i / 2) * 6 + 2 + i % 2] = *v;
+        }
+        let faces = CubeMapLayout::VerticalCross
+            .extract_faces(&bytes, 6, 8, 1)
+            .unwrap();
+        assert_eq!(faces[5], vec![4, 3, 2, 1]);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{gl, GLchar, GLenum, GLsizei, GLuint, StateCache};
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+type DebugHandler = Box<dyn Fn(&DebugMessage)>;
+
+thread_local! {
+    static HANDLER: RefCell<Option<DebugHandler>> = RefCell::new(None);
+}
+
+/// Sources of the debug messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugSource {
+    Api = gl::DEBUG_SOURCE_API as isize,
+    WindowSystem = gl::DEBUG_SOURCE_WINDOW_SYSTEM as isize,
+    ShaderCompiler = gl::DEBUG_SOURCE_SHADER_COMPILER as isize,
+    ThirdParty = gl::DEBUG_SOURCE_THIRD_PARTY as isize,
+    Application = gl::DEBUG_SOURCE_APPLICATION as isize,
+    Other = gl::DEBUG_SOURCE_OTHER as isize,
+}
+
+impl DebugSource {
+    /// Returns the variant of the raw `GL_DEBUG_SOURCE_*` value.
+    pub fn from_raw(value: GLenum) -> Option<Self> {
+        match value {
+            gl::DEBUG_SOURCE_API => Some(DebugSource::Api),
+            gl::DEBUG_SOURCE_WINDOW_SYSTEM => Some(DebugSource::WindowSystem),
+            gl::DEBUG_SOURCE_SHADER_COMPILER => Some(DebugSource::ShaderCompiler),
+            gl::DEBUG_SOURCE_THIRD_PARTY => Some(DebugSource::ThirdParty),
+            gl::DEBUG_SOURCE_APPLICATION => Some(DebugSource::Application),
+            gl::DEBUG_SOURCE_OTHER => Some(DebugSource::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Types of the debug messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugType {
+    Error = gl::DEBUG_TYPE_ERROR as isize,
+    DeprecatedBehavior = gl::DEBUG_TYPE_DEPRECATED_BEHAVIOR as isize,
+    UndefinedBehavior = gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR as isize,
+    Portability = gl::DEBUG_TYPE_PORTABILITY as isize,
+    Performance = gl::DEBUG_TYPE_PERFORMANCE as isize,
+    Marker = gl::DEBUG_TYPE_MARKER as isize,
+    PushGroup = gl::DEBUG_TYPE_PUSH_GROUP as isize,
+    PopGroup = gl::DEBUG_TYPE_POP_GROUP as isize,
+    Other = gl::DEBUG_TYPE_OTHER as isize,
+}
+
+impl DebugType {
+    /// Returns the variant of the raw `GL_DEBUG_TYPE_*` value.
+    pub fn from_raw(value: GLenum) -> Option<Self> {
+        match value {
+            gl::DEBUG_TYPE_ERROR => Some(DebugType::Error),
+            gl::DEBUG_TYPE_DEPRECATED_BEHAVIOR => Some(DebugType::DeprecatedBehavior),
+            gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR => Some(DebugType::UndefinedBehavior),
+            gl::DEBUG_TYPE_PORTABILITY => Some(DebugType::Portability),
+            gl::DEBUG_TYPE_PERFORMANCE => Some(DebugType::Performance),
+            gl::DEBUG_TYPE_MARKER => Some(DebugType::Marker),
+            gl::DEBUG_TYPE_PUSH_GROUP => Some(DebugType::PushGroup),
+            gl::DEBUG_TYPE_POP_GROUP => Some(DebugType::PopGroup),
+            gl::DEBUG_TYPE_OTHER => Some(DebugType::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Severities of the debug messages, from the most severe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugSeverity {
+    High = gl::DEBUG_SEVERITY_HIGH as isize,
+    Medium = gl::DEBUG_SEVERITY_MEDIUM as isize,
+    Low = gl::DEBUG_SEVERITY_LOW as isize,
+    Notification = gl::DEBUG_SEVERITY_NOTIFICATION as isize,
+}
+
+impl DebugSeverity {
+    /// Returns the variant of the raw `GL_DEBUG_SEVERITY_*` value.
+    pub fn from_raw(value: GLenum) -> Option<Self> {
+        match value {
+            gl::DEBUG_SEVERITY_HIGH => Some(DebugSeverity::High),
+            gl::DEBUG_SEVERITY_MEDIUM => Some(DebugSeverity::Medium),
+            gl::DEBUG_SEVERITY_LOW => Some(DebugSeverity::Low),
+            gl::DEBUG_SEVERITY_NOTIFICATION => Some(DebugSeverity::Notification),
+            _ => None,
+        }
+    }
+
+    /// Returns the log level the messages of the severity are logged with.
+    pub fn level(self) -> log::Level {
+        match self {
+            DebugSeverity::High => log::Level::Error,
+            DebugSeverity::Medium => log::Level::Warn,
+            DebugSeverity::Low => log::Level::Info,
+            DebugSeverity::Notification => log::Level::Debug,
+        }
+    }
+}
+
+/// Message received from the debug output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugMessage {
+    pub source: DebugSource,
+    pub type_: DebugType,
+    pub id: GLuint,
+    pub severity: DebugSeverity,
+    pub message: String,
+}
+
+/// Debug output of the current context, see `KHR_debug`.
+///
+/// The messages are delivered synchronously on the thread of the GL call
+/// which raised them, so the handlers are installed per thread.
+#[derive(Debug)]
+pub struct DebugOutput;
+
+impl DebugOutput {
+    /// Route the debug messages to the `log` crate, with the level mapped
+    /// from the severity.
+    pub fn enable_log() {
+        Self::enable_with(|msg| {
+            log::log!(
+                msg.severity.level(),
+                "GL {:?} {:?} [{}]: {}",
+                msg.source,
+                msg.type_,
+                msg.id,
+                msg.message
+            )
+        });
+    }
+
+    /// Route the debug messages to the `handler`.
+    ///
+    /// The handler runs inside the GL call which raised the message, often
+    /// while the `StateCache` is borrowed, so it must not make GL calls or
+    /// bind any `Bindable`. Collect the messages and handle them after the
+    /// call instead. A panic of the handler is caught and logged.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn enable_with<F>(handler: F)
+    where
+        F: Fn(&DebugMessage) + 'static,
+    {
+        HANDLER.with(|h| *h.borrow_mut() = Some(Box::new(handler)));
+        StateCache::with(|cache| {
+            cache.set_enabled(gl::DEBUG_OUTPUT, true);
+            cache.set_enabled(gl::DEBUG_OUTPUT_SYNCHRONOUS, true);
+        });
+        crate::debug_message_callback(Some(debug_callback), std::ptr::null());
+    }
+
+    /// Stop the debug output and drop the handler.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn disable() {
+        crate::debug_message_callback(None, std::ptr::null());
+        StateCache::with(|cache| cache.set_enabled(gl::DEBUG_OUTPUT, false));
+        HANDLER.with(|h| h.borrow_mut().take());
+    }
+
+    /// Enable or disable the messages matching the filter, `None` matches all.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_filter(
+        source: Option<DebugSource>,
+        type_: Option<DebugType>,
+        severity: Option<DebugSeverity>,
+        enabled: bool,
+    ) {
+        crate::debug_message_control(
+            source.map_or(gl::DONT_CARE, |v| v as GLenum),
+            type_.map_or(gl::DONT_CARE, |v| v as GLenum),
+            severity.map_or(gl::DONT_CARE, |v| v as GLenum),
+            &[],
+            enabled,
+        );
+    }
+
+    /// Insert an application message into the debug output, which is also
+    /// recorded as a marker by the debuggers.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn insert(type_: DebugType, severity: DebugSeverity, id: GLuint, message: &str) {
+        crate::debug_message_insert(
+            gl::DEBUG_SOURCE_APPLICATION,
+            type_ as GLenum,
+            id,
+            severity as GLenum,
+            message,
+        );
+    }
+}
+
+extern "system" fn debug_callback(
+    source: GLenum,
+    type_: GLenum,
+    id: GLuint,
+    severity: GLenum,
+    length: GLsizei,
+    message: *const GLchar,
+    _user_param: *mut c_void,
+) {
+    let message = if message.is_null() {
+        String::new()
+    } else if length < 0 {
+        unsafe { CStr::from_ptr(message) }
+            .to_string_lossy()
+            .into_owned()
+    } else {
+        let bytes = unsafe { std::slice::from_raw_parts(message as *const u8, length as usize) };
+        String::from_utf8_lossy(bytes).into_owned()
+    };
+    let msg = DebugMessage {
+        source: DebugSource::from_raw(source).unwrap_or(DebugSource::Other),
+        type_: DebugType::from_raw(type_).unwrap_or(DebugType::Other),
+        id,
+        severity: DebugSeverity::from_raw(severity).unwrap_or(DebugSeverity::Notification),
+        message,
+    };
+    let _ = HANDLER.try_with(|h| {
+        // Messages raised by the handler itself are dropped.
+        if let Ok(handler) = h.try_borrow_mut() {
+            if let Some(ref handler) = *handler {
+                // Unwinding out of the extern callback would abort the process.
+                if catch_unwind(AssertUnwindSafe(|| handler(&msg))).is_err() {
+                    log::error!("GL debug message handler panicked on: {}", msg.message);
+                }
+            }
+        }
+    });
+}
+
+/// Named group of the GL commands, shown as a region in the debuggers like
+/// RenderDoc and apitrace, the group is popped when dropped.
+#[derive(Debug)]
+pub struct DebugGroup {
+    _private: (),
+}
+
+impl DebugGroup {
+    /// Push a group named `message`.
+    pub fn push(message: &str) -> Self {
+        Self::with_id(0, message)
+    }
+
+    /// Push a group named `message` with the application defined `id`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn with_id(id: GLuint, message: &str) -> Self {
+        crate::push_debug_group(gl::DEBUG_SOURCE_APPLICATION, id, message);
+        Self { _private: () }
+    }
+}
+
+impl Drop for DebugGroup {
+    fn drop(&mut self) {
+        crate::pop_debug_group();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_raw() {
+        for source in [
+            DebugSource::Api,
+            DebugSource::Application,
+            DebugSource::Other,
+        ] {
+            assert_eq!(DebugSource::from_raw(source as GLenum), Some(source));
+        }
+        for type_ in [DebugType::Error, DebugType::PushGroup, DebugType::Other] {
+            assert_eq!(DebugType::from_raw(type_ as GLenum), Some(type_));
+        }
+        assert_eq!(
+            DebugSeverity::from_raw(gl::DEBUG_SEVERITY_HIGH),
+            Some(DebugSeverity::High)
+        );
+        assert_eq!(DebugSeverity::from_raw(gl::NONE), None);
+        assert_eq!(DebugSeverity::Medium.level(), log::Level::Warn);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{EglImage, Texture, YuvMatrix, YuvRange};
+use egls::{egl, EGLDisplay, EGLenum, EGLint};
+use std::os::unix::io::RawFd;
+
+// EGL_EXT_image_dma_buf_import
+const LINUX_DMA_BUF_EXT: EGLenum = 0x3270;
+const LINUX_DRM_FOURCC_EXT: EGLint = 0x3271;
+const YUV_COLOR_SPACE_HINT_EXT: EGLint = 0x327B;
+const SAMPLE_RANGE_HINT_EXT: EGLint = 0x327C;
+const ITU_REC601_EXT: EGLint = 0x327F;
+const ITU_REC709_EXT: EGLint = 0x3280;
+const YUV_FULL_RANGE_EXT: EGLint = 0x3282;
+const YUV_NARROW_RANGE_EXT: EGLint = 0x3283;
+
+/// Attribute names of each plane as (fd, offset, pitch, modifier lo, modifier hi).
+const PLANE_ATTRIBS: [[EGLint; 5]; 4] = [
+    [0x3272, 0x3273, 0x3274, 0x3443, 0x3444],
+    [0x3275, 0x3276, 0x3277, 0x3445, 0x3446],
+    [0x3278, 0x3279, 0x327A, 0x3447, 0x3448],
+    [0x3440, 0x3441, 0x3442, 0x3449, 0x344A],
+];
+
+/// Returns the DRM fourcc code of the four characters, e.g. `fourcc(b"NV12")`.
+pub const fn fourcc(code: &[u8; 4]) -> u32 {
+    code[0] as u32 | (code[1] as u32) << 8 | (code[2] as u32) << 16 | (code[3] as u32) << 24
+}
+
+/// One plane of a dma-buf image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DmaBufPlane {
+    /// The dma-buf file descriptor, which is not owned by the plane.
+    pub fd: RawFd,
+    /// Offset of the plane from the start of the dma-buf in bytes.
+    pub offset: u32,
+    /// Bytes between the start of two rows.
+    pub pitch: u32,
+    /// DRM format modifier, requires `EGL_EXT_image_dma_buf_import_modifiers`.
+    pub modifier: Option<u64>,
+}
+
+impl DmaBufPlane {
+    pub fn new(fd: RawFd, offset: u32, pitch: u32) -> Self {
+        Self {
+            fd,
+            offset,
+            pitch,
+            modifier: None,
+        }
+    }
+
+    pub fn with_modifier(mut self, modifier: u64) -> Self {
+        self.modifier = Some(modifier);
+        self
+    }
+}
+
+/// Importer which wraps Linux dma-buf into textures without copies.
+///
+/// The image is created with `EGL_EXT_image_dma_buf_import` and sampled from a
+/// `TextureExternalOES` texture, the image is destroyed with the texture.
+/// # Note
+/// A current EGL context is required, the display of the context is used if
+/// none is specified with `with_display()`.
+#[derive(Clone, Debug)]
+pub struct DmaBufImporter {
+    display: Option<EGLDisplay>,
+    width: usize,
+    height: usize,
+    fourcc: u32,
+    planes: Vec<DmaBufPlane>,
+    color_space: Option<(YuvMatrix, YuvRange)>,
+}
+
+impl DmaBufImporter {
+    /// Construct an importer for `width` x `height` image with DRM `fourcc` format.
+    pub fn new(width: usize, height: usize, fourcc: u32) -> Self {
+        Self {
+            display: None,
+            width,
+            height,
+            fourcc,
+            planes: Vec::new(),
+            color_space: None,
+        }
+    }
+
+    pub fn with_display(mut self, display: EGLDisplay) -> Self {
+        self.display = Some(display);
+        self
+    }
+
+    /// Append a plane, up to four planes are supported.
+    pub fn with_plane(mut self, plane: DmaBufPlane) -> Self {
+        self.planes.push(plane);
+        self
+    }
+
+    /// Set the YUV color space hints used by the driver for the conversion.
+    pub fn with_color_space(mut self, matrix: YuvMatrix, range: YuvRange) -> Self {
+        self.color_space = Some((matrix, range));
+        self
+    }
+
+    /// Returns the attribute list passed to `eglCreateImageKHR`.
+    pub fn attributes(&self) -> Result<Vec<EGLint>, String> {
+        if self.planes.is_empty() || self.planes.len() > PLANE_ATTRIBS.len() {
+            return Err(format!(
+                "Dma-buf requires 1 to {} planes, but got {}!",
+                PLANE_ATTRIBS.len(),
+                self.planes.len()
+            ));
+        }
+        let mut attribs = vec![
+            egl::WIDTH as EGLint,
+            self.width as EGLint,
+            egl::HEIGHT as EGLint,
+            self.height as EGLint,
+            LINUX_DRM_FOURCC_EXT,
+            self.fourcc as EGLint,
+        ];
+        for (plane, names) in self.planes.iter().zip(PLANE_ATTRIBS.iter()) {
+            attribs.extend_from_slice(&[
+                names[0],
+                plane.fd,
+                names[1],
+                plane.offset as EGLint,
+                names[2],
+                plane.pitch as EGLint,
+            ]);
+            if let Some(modifier) = plane.modifier {
+                attribs.extend_from_slice(&[
+                    names[3],
+                    modifier as u32 as EGLint,
+                    names[4],
+                    (modifier >> 32) as u32 as EGLint,
+                ]);
+            }
+        }
+        if let Some((matrix, range)) = self.color_space {
+            let matrix = match matrix {
+                YuvMatrix::Bt601 => ITU_REC601_EXT,
+                YuvMatrix::Bt709 => ITU_REC709_EXT,
+            };
+            let range = match range {
+                YuvRange::Full => YUV_FULL_RANGE_EXT,
+                YuvRange::Limited => YUV_NARROW_RANGE_EXT,
+            };
+            attribs.extend_from_slice(&[
+                YUV_COLOR_SPACE_HINT_EXT,
+                matrix,
+                SAMPLE_RANGE_HINT_EXT,
+                range,
+            ]);
+        }
+        attribs.push(egl::NONE as EGLint);
+        Ok(attribs)
+    }
+
+    /// Create an EGLImage which refers to the dma-buf.
+    /// # Note
+    /// The file descriptors may be closed after the import, the EGLImage keeps
+    /// its own reference to the dma-buf.
+    pub fn create_image(&self) -> Result<EglImage<'static>, String> {
+        let attribs = self.attributes()?;
+        let display = match self.display {
+            Some(display) => display,
+            None => unsafe { egl::GetCurrentDisplay() },
+        };
+        if display == egl::NO_DISPLAY {
+            return Err("No current EGL display!".to_string());
+        }
+        let image = egls::create_image_khr(
+            display,
+            egl::NO_CONTEXT,
+            LINUX_DMA_BUF_EXT,
+            std::ptr::null_mut(),
+            Some(&attribs),
+        )
+        .map_err(|e| format!("Create EGLImage from dma-buf failed: {}!", e.to_str()))?;
+        Ok(unsafe { EglImage::from_raw(display, image, self.width, self.height) })
+    }
+
+    /// Import the dma-buf as an external texture, the image is destroyed with the texture.
+    pub fn load<'a>(&self) -> Result<Texture<'a>, String> {
+        self.create_image()?.load_texture()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attributes() {
+        assert_eq!(fourcc(b"NV12"), 0x3231_564E);
+        let importer = DmaBufImporter::new(64, 32, fourcc(b"NV12"))
+            .with_plane(DmaBufPlane::new(5, 0, 64))
+            .with_plane(DmaBufPlane::new(5, 2048, 64).with_modifier(0x0100_0000_0000_0001));
+        let attribs = importer.attributes().unwrap();
+        assert_eq!(attribs.len(), 6 + 6 + 10 + 1);
+        assert_eq!(&attribs[12..16], &[0x3275, 5, 0x3276, 2048]);
+        assert_eq!(&attribs[18..22], &[0x3445, 1, 0x3446, 0x0100_0000]);
+        assert_eq!(attribs[22], egl::NONE as EGLint);
+        assert!(DmaBufImporter::new(64, 32, 0).attributes().is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{GLeglImageOES, Texture, TextureLoader, TextureTarget};
+use egls::{egl, EGLDisplay, EGLImageKHR, EGLint};
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+struct ImageHandle {
+    display: EGLDisplay,
+    image: EGLImageKHR,
+    width: usize,
+    height: usize,
+}
+
+impl Drop for ImageHandle {
+    fn drop(&mut self) {
+        let _ = egls::destroy_image_khr(self.display, self.image);
+    }
+}
+
+/// Shared EGLImage which is destroyed when the last reference dropped.
+///
+/// The lifetime `'a` borrows the source of the image, so the image never
+/// outlives its source, every consumer (texture or renderbuffer) made from the
+/// image holds a reference, so the image never dies before its consumers.
+#[derive(Clone)]
+pub struct EglImage<'a> {
+    handle: Rc<ImageHandle>,
+    _source: PhantomData<&'a ()>,
+}
+
+impl EglImage<'static> {
+    /// Take the ownership of `width` x `height` `image` created on `display`.
+    ///
+    /// # Safety
+    /// The `image` must be a valid EGLImage which is not destroyed elsewhere.
+    pub unsafe fn from_raw(
+        display: EGLDisplay,
+        image: EGLImageKHR,
+        width: usize,
+        height: usize,
+    ) -> Self {
+        Self::with_handle(display, image, width, height)
+    }
+}
+
+impl<'a> EglImage<'a> {
+    fn with_handle(display: EGLDisplay, image: EGLImageKHR, width: usize, height: usize) -> Self {
+        Self {
+            handle: Rc::new(ImageHandle {
+                display,
+                image,
+                width,
+                height,
+            }),
+            _source: PhantomData,
+        }
+    }
+
+    /// Create an EGLImage from the level 0 of the 2D `texture`.
+    ///
+    /// # Note
+    /// Requires `EGL_KHR_gl_texture_2D_image` and a current EGL context which
+    /// owns the texture, the texture must be complete.
+    pub fn from_texture(texture: &'a Texture<'_>) -> Result<Self, String> {
+        if !matches!(texture.target(), TextureTarget::Texture2D) {
+            return Err(format!(
+                "EGLImage requires a 2D texture, but got {:?}!",
+                texture.target()
+            ));
+        }
+        let (display, context) = unsafe { (egl::GetCurrentDisplay(), egl::GetCurrentContext()) };
+        if display == egl::NO_DISPLAY || context == egl::NO_CONTEXT {
+            return Err("No current EGL context!".to_string());
+        }
+        let attribs = [
+            egl::GL_TEXTURE_LEVEL as EGLint,
+            0,
+            egl::IMAGE_PRESERVED_KHR as EGLint,
+            egl::TRUE as EGLint,
+            egl::NONE as EGLint,
+        ];
+        let image = egls::create_image_khr(
+            display,
+            context,
+            egl::GL_TEXTURE_2D,
+            texture.id() as usize as egl::EGLClientBuffer,
+            Some(&attribs),
+        )
+        .map_err(|e| format!("Create EGLImage from texture failed: {}!", e.to_str()))?;
+        Ok(Self::with_handle(
+            display,
+            image,
+            texture.width(),
+            texture.height(),
+        ))
+    }
+
+    /// Returns the raw EGLImage handle, e.g. for other contexts or encoders.
+    pub fn as_raw(&self) -> GLeglImageOES {
+        self.handle.image as GLeglImageOES
+    }
+
+    /// Returns the display which the image is created on.
+    pub fn display(&self) -> EGLDisplay {
+        self.handle.display
+    }
+
+    pub fn width(&self) -> usize {
+        self.handle.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.handle.height
+    }
+
+    /// Returns the number of references includes this one and all consumers.
+    pub fn ref_count(&self) -> usize {
+        Rc::strong_count(&self.handle)
+    }
+
+    /// Load an external texture which samples the image.
+    ///
+    /// The texture keeps the image alive until dropped.
+    pub fn load_texture(&self) -> Result<Texture<'a>, String> {
+        let image = self.clone();
+        TextureLoader::default()
+            .with_egl_image(self.as_raw())
+            .with_size(self.width(), self.height())
+            .with_linear()
+            .with_finalizer(move |_| {
+                let _ = &image;
+            })
+            .load()
+    }
+}
+
+impl<'a> fmt::Debug for EglImage<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EglImage")
+            .field("display", &self.handle.display)
+            .field("image", &self.handle.image)
+            .field("width", &self.handle.width)
+            .field("height", &self.handle.height)
+            .finish()
+    }
+}
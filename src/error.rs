@@ -5,6 +5,11 @@ use crate::{
     },
     GLenum,
 };
+#[cfg(feature = "checked")]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature = "checked")]
+static PANIC_ON_ERROR: AtomicBool = AtomicBool::new(true);
 
 /// Error Object for OpenGL.
 #[derive(Clone, Copy, Default, PartialEq, Eq)]
@@ -63,3 +68,97 @@ impl std::error::Error for Error {
         None
     }
 }
+
+/// Set whether the errors found by the `checked` feature panic, otherwise
+/// they are logged with `log::error!`, panic by default.
+///
+/// The reported location is the first caller outside of gls when the call
+/// is made through the apis, or directly by the methods of the wrapper types.
+/// Calls made by `Bindable` implementations, `Drop` and the `StateCache`
+/// report the location inside gls.
+#[cfg(feature = "checked")]
+pub fn set_panic_on_error(panic: bool) {
+    PANIC_ON_ERROR.store(panic, Ordering::Relaxed);
+}
+
+/// Report the pending errors raised by the call `name` with `args`.
+#[cfg(feature = "checked")]
+#[track_caller]
+pub(crate) fn check_error(name: &str, args: &[(&str, &dyn std::fmt::Debug)]) {
+    report_errors(name, args, Error::new);
+}
+
+/// Report the errors returned by `next_error` until no error, at most 8.
+#[cfg(feature = "checked")]
+#[track_caller]
+fn report_errors<F>(name: &str, args: &[(&str, &dyn std::fmt::Debug)], mut next_error: F)
+where
+    F: FnMut() -> Error,
+{
+    let mut errors = Vec::new();
+    // Bounded in case of no current context which may report errors forever.
+    while errors.len() < 8 {
+        match next_error() {
+            e if e.is_error() => errors.push(e),
+            _ => break,
+        }
+    }
+    if errors.is_empty() {
+        return;
+    }
+    let args: Vec<String> = args
+        .iter()
+        .map(|(name, value)| format!("{}: {:?}", name, value))
+        .collect();
+    let message = format!(
+        "{:?} in {}({}) at {}",
+        errors,
+        name,
+        args.join(", "),
+        std::panic::Location::caller()
+    );
+    if PANIC_ON_ERROR.load(Ordering::Relaxed) {
+        panic!("{}", message);
+    } else {
+        log::error!("{}", message);
+    }
+}
+
+#[cfg(all(test, feature = "checked"))]
+mod tests {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn test_report_errors() {
+        let (target, texture) = (0x0DE1, 7);
+        let args: [(&str, &dyn std::fmt::Debug); 2] = [("target", &target), ("texture", &texture)];
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut codes = vec![NO_ERROR, INVALID_ENUM];
+            report_errors("bind_texture", &args, || Error(codes.pop().unwrap()));
+        }));
+        let line = line!() - 2;
+        let payload = result.unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("[Error(1280, "));
+        assert!(message.contains(" in bind_texture(target: 3553, texture: 7) at "));
+        // The location is the caller of the check.
+        assert!(message.contains(&format!(" at {}:{}:", file!(), line)));
+
+        // No error, and at most 8 errors are drained from a lost context.
+        report_errors("flush", &[], || Error(NO_ERROR));
+        let mut count = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            report_errors("flush", &[], || {
+                count += 1;
+                Error(INVALID_OPERATION)
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(count, 8);
+
+        set_panic_on_error(false);
+        report_errors("flush", &[], || Error(INVALID_VALUE));
+        set_panic_on_error(true);
+    }
+}
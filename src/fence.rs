@@ -0,0 +1,120 @@
+use crate::{gl, GLenum, GLint, GLsync, GLuint64};
+use std::time::Duration;
+
+/// Result of waiting for a Fence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FenceStatus {
+    /// The GPU has passed the fence.
+    Signaled,
+    /// The fence is still unsignaled after the timeout.
+    TimeoutExpired,
+    /// The wait failed, e.g. the context is lost.
+    Failed,
+}
+
+impl FenceStatus {
+    /// Returns the status of the raw value returned by `glClientWaitSync`.
+    pub fn from_raw(value: GLenum) -> Self {
+        match value {
+            gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => FenceStatus::Signaled,
+            gl::TIMEOUT_EXPIRED => FenceStatus::TimeoutExpired,
+            _ => FenceStatus::Failed,
+        }
+    }
+}
+
+/// Fence sync object which is signaled when the GPU completes all commands
+/// issued before it.
+#[derive(Debug)]
+pub struct Fence {
+    sync: GLsync,
+}
+
+impl Fence {
+    /// Insert a new fence into the command stream.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn new() -> Self {
+        Self {
+            sync: crate::fence_sync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0),
+        }
+    }
+
+    /// Returns the raw sync object of the Fence.
+    pub fn as_raw(&self) -> GLsync {
+        self.sync
+    }
+
+    /// Block the CPU until the fence is signaled or `timeout` expired.
+    ///
+    /// Pending commands are flushed, so the fence is signaled eventually even
+    /// without a `flush()` after the fence inserted.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn wait(&self, timeout: Duration) -> FenceStatus {
+        let status = crate::client_wait_sync(
+            self.sync,
+            gl::SYNC_FLUSH_COMMANDS_BIT,
+            timeout_nanos(timeout),
+        );
+        FenceStatus::from_raw(status)
+    }
+
+    /// Make the GPU wait for the fence before executing further commands,
+    /// the CPU is not blocked.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn wait_gpu(&self) {
+        crate::wait_sync(self.sync, 0, gl::TIMEOUT_IGNORED);
+    }
+
+    /// Returns `true` if the fence is signaled, never blocks.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn is_signaled(&self) -> bool {
+        crate::get_synciv(self.sync, gl::SYNC_STATUS) == gl::SIGNALED as GLint
+    }
+}
+
+impl Default for Fence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Fence {
+    fn drop(&mut self) {
+        crate::delete_sync(self.sync);
+    }
+}
+
+/// Returns the `timeout` in nanoseconds, clamped to the range of `GLuint64`.
+fn timeout_nanos(timeout: Duration) -> GLuint64 {
+    timeout.as_nanos().min(GLuint64::MAX as u128) as GLuint64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_raw() {
+        assert_eq!(
+            FenceStatus::from_raw(gl::ALREADY_SIGNALED),
+            FenceStatus::Signaled
+        );
+        assert_eq!(
+            FenceStatus::from_raw(gl::CONDITION_SATISFIED),
+            FenceStatus::Signaled
+        );
+        assert_eq!(
+            FenceStatus::from_raw(gl::TIMEOUT_EXPIRED),
+            FenceStatus::TimeoutExpired
+        );
+        assert_eq!(FenceStatus::from_raw(gl::WAIT_FAILED), FenceStatus::Failed);
+        assert_eq!(FenceStatus::from_raw(gl::NONE), FenceStatus::Failed);
+    }
+
+    #[test]
+    fn test_timeout_nanos() {
+        assert_eq!(timeout_nanos(Duration::from_millis(5)), 5_000_000);
+        assert_eq!(timeout_nanos(Duration::ZERO), 0);
+        assert_eq!(timeout_nanos(Duration::MAX), GLuint64::MAX);
+    }
+}
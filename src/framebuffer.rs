@@ -1,6 +1,6 @@
 use crate::gl::{GLenum, GLuint};
 use crate::prelude::*;
-use crate::{gl, Texture, TextureFormat, TextureLoader};
+use crate::{gl, CompareFunc, StateCache, Texture, TextureFormat, TextureLoader};
 
 /// Framebuffer wrapper.
 #[derive(Debug)]
@@ -12,16 +12,17 @@ impl Framebuffer {
     /// Create a Framebuffer Wrapper.
     /// # Note
     /// The object is unbinded after generated.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn new() -> Self {
         let mut ids: [GLuint; 1] = [0];
         crate::gen_framebuffers(&mut ids);
-        crate::bind_framebuffer(gl::FRAMEBUFFER, ids[0]);
+        StateCache::with(|cache| cache.bind_framebuffer(gl::FRAMEBUFFER, ids[0]));
         Self { id: ids[0] }
     }
 
     /// Returns the FrameBuffer object of the screen surface.
     pub fn with_screen() -> Self {
-        crate::bind_framebuffer(gl::FRAMEBUFFER, 0);
+        StateCache::with(|cache| cache.bind_framebuffer(gl::FRAMEBUFFER, 0));
         Self { id: 0 }
     }
 
@@ -30,7 +31,20 @@ impl Framebuffer {
         self.id
     }
 
+    /// Set the label of the Framebuffer shown in the debug messages and the debuggers.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_label(&self, label: &str) {
+        crate::object_label(gl::FRAMEBUFFER, self.id(), label);
+    }
+
+    /// Returns the label of the Framebuffer, empty if not labeled.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn label(&self) -> String {
+        crate::get_object_label(gl::FRAMEBUFFER, self.id())
+    }
+
     /// Set color attachment with `texture`.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_color_texture(&self, texture: GLuint) {
         if self.id != 0 {
             // self.bind();
@@ -46,6 +60,7 @@ impl Framebuffer {
     }
 
     /// Set depth attachment with `texture`.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_depth_texture(&self, texture: GLuint) {
         if self.id != 0 {
             // self.bind();
@@ -60,7 +75,48 @@ impl Framebuffer {
         }
     }
 
+    /// Set combined depth and stencil attachment with `texture`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_depth_stencil_texture(&self, texture: GLuint) {
+        if self.id != 0 {
+            crate::framebuffer_texture2d(
+                gl::FRAMEBUFFER,
+                gl::DEPTH_STENCIL_ATTACHMENT,
+                gl::TEXTURE_2D,
+                texture,
+                0,
+            );
+        }
+    }
+
+    /// Set color attachment with `renderbuffer`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_color_renderbuffer(&self, renderbuffer: GLuint) {
+        if self.id != 0 {
+            crate::framebuffer_renderbuffer(
+                gl::FRAMEBUFFER,
+                gl::COLOR_ATTACHMENT0,
+                gl::RENDERBUFFER,
+                renderbuffer,
+            );
+        }
+    }
+
+    /// Set depth attachment with `renderbuffer`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_depth_renderbuffer(&self, renderbuffer: GLuint) {
+        if self.id != 0 {
+            crate::framebuffer_renderbuffer(
+                gl::FRAMEBUFFER,
+                gl::DEPTH_ATTACHMENT,
+                gl::RENDERBUFFER,
+                renderbuffer,
+            );
+        }
+    }
+
     /// Set stencil attachment with `texture`.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_stencil_texture(&self, texture: GLuint) {
         if self.id != 0 {
             self.bind();
@@ -86,6 +142,7 @@ impl Drop for Framebuffer {
     fn drop(&mut self) {
         if self.id != 0 {
             self.unbind();
+            StateCache::with(|cache| cache.forget_framebuffer(self.id));
             crate::delete_framebuffers(&[self.id]);
         }
     }
@@ -93,23 +150,23 @@ impl Drop for Framebuffer {
 
 impl Bindable for Framebuffer {
     fn bind(&self) {
-        crate::bind_framebuffer(gl::FRAMEBUFFER, self.id);
+        StateCache::with(|cache| cache.bind_framebuffer(gl::FRAMEBUFFER, self.id));
     }
 
     fn bind_at(&self, target: u32) {
-        crate::bind_framebuffer(target as GLenum, self.id);
+        StateCache::with(|cache| cache.bind_framebuffer(target as GLenum, self.id));
     }
 
     fn unbind(&self) {
-        crate::bind_framebuffer(gl::FRAMEBUFFER, 0);
+        StateCache::with(|cache| cache.unbind_framebuffer(gl::FRAMEBUFFER, self.id));
     }
 
     fn unbind_at(&self, target: u32) {
-        crate::bind_framebuffer(target as GLenum, 0);
+        StateCache::with(|cache| cache.unbind_framebuffer(target as GLenum, self.id));
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FramebufferAttachment {
     NoAttachment,
     CombinedDepthStencil,
@@ -128,12 +185,14 @@ pub struct FramebufferObject<'a> {
 }
 
 impl<'a> FramebufferObject<'a> {
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn new(width: usize, height: usize, texture_format: TextureFormat) -> Self {
         let fb = Framebuffer::new();
         let texture = TextureLoader::default()
             .with_size(width, height)
             .with_internal_format(texture_format)
-            .with_format(texture_format)
+            .with_format(texture_format.external_format())
+            .with_texel(texture_format.default_texel())
             .with_linear()
             .with_allocate_storage()
             .load()
@@ -160,6 +219,52 @@ impl<'a> FramebufferObject<'a> {
         Self::new(width, height, TextureFormat::Rgba)
     }
 
+    /// Create a depth-only framebuffer object with a depth `texture_format`.
+    ///
+    /// The depth texture is sampled with depth comparison enabled, suitable
+    /// for `sampler2DShadow` in shadow mapping.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn with_depth_texture(
+        width: usize,
+        height: usize,
+        texture_format: TextureFormat,
+    ) -> Result<Self, String> {
+        if !texture_format.is_depth() {
+            return Err(format!("{:?} is not a depth format!", texture_format));
+        }
+        let fb = Framebuffer::new();
+        let texture = TextureLoader::default()
+            .with_size(width, height)
+            .with_internal_format(texture_format)
+            .with_nearest()
+            .with_compare(CompareFunc::LessEqual)
+            .with_allocate_storage()
+            .load()?;
+        let attachment = match texture_format.external_format() {
+            TextureFormat::DepthStencil => {
+                fb.set_depth_stencil_texture(texture.id());
+                FramebufferAttachment::CombinedDepthStencil
+            }
+            _ => {
+                fb.set_depth_texture(texture.id());
+                FramebufferAttachment::Depth
+            }
+        };
+        crate::draw_buffers(&[gl::NONE]);
+        crate::read_buffer(gl::NONE);
+        match crate::check_framebuffer_status(gl::FRAMEBUFFER) {
+            gl::FRAMEBUFFER_COMPLETE => Ok(Self {
+                width,
+                height,
+                texture_format,
+                attachment,
+                fb,
+                texture,
+            }),
+            status => Err(format!("Framebuffer is not complete: {:#x}!", status)),
+        }
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -168,6 +273,14 @@ impl<'a> FramebufferObject<'a> {
         self.height
     }
 
+    pub fn texture_format(&self) -> TextureFormat {
+        self.texture_format
+    }
+
+    pub fn attachment(&self) -> FramebufferAttachment {
+        self.attachment
+    }
+
     pub fn framebuffer(&self) -> &Framebuffer {
         &self.fb
     }
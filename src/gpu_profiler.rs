@@ -0,0 +1,387 @@
+use crate::{Query, QueryTarget};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::ops::{Deref, DerefMut};
+
+/// GPU timing of a profiler scope.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScopeTiming {
+    /// Name of the scope.
+    pub name: String,
+    /// Index of the frame which the scope belongs to.
+    pub frame: u64,
+    /// Nesting depth of the scope, 0 for the outermost scopes.
+    pub depth: usize,
+    /// GPU time in nanoseconds when the scope started.
+    pub start: u64,
+    /// GPU time in nanoseconds spent in the scope.
+    pub duration: u64,
+}
+
+/// Timestamp query of a scope, `Query` on the GPU.
+trait Timestamp {
+    fn is_available(&self) -> bool;
+    fn result(&self) -> u64;
+}
+
+impl Timestamp for Query {
+    fn is_available(&self) -> bool {
+        Query::is_available(self)
+    }
+
+    fn result(&self) -> u64 {
+        Query::result(self)
+    }
+}
+
+#[derive(Debug)]
+struct PendingScope<Q> {
+    name: String,
+    depth: usize,
+    begin: Q,
+    end: Option<Q>,
+}
+
+#[derive(Debug)]
+struct PendingFrame<Q> {
+    index: u64,
+    scopes: Vec<PendingScope<Q>>,
+}
+
+impl<Q> PendingFrame<Q> {
+    fn new(index: u64) -> Self {
+        Self {
+            index,
+            scopes: Vec::new(),
+        }
+    }
+}
+
+/// Finished frames waiting for their timestamps, with the recycled queries.
+#[derive(Debug)]
+struct FrameQueue<Q> {
+    latency: usize,
+    pending: VecDeque<PendingFrame<Q>>,
+    pool: Vec<Q>,
+    timings: Vec<ScopeTiming>,
+}
+
+impl<Q: Timestamp> FrameQueue<Q> {
+    fn new(latency: usize) -> Self {
+        Self {
+            latency,
+            pending: VecDeque::new(),
+            pool: Vec::new(),
+            timings: Vec::new(),
+        }
+    }
+
+    /// Queue the finished `frame` and collect the frames older than the
+    /// latency whose timestamps are available, or drop all pending frames if
+    /// the timer was `disjoint`.
+    fn push(&mut self, frame: PendingFrame<Q>, disjoint: bool) {
+        self.pending.push_back(frame);
+        if disjoint {
+            // The timer is unreliable, drop the results of all pending frames.
+            while let Some(frame) = self.pending.pop_front() {
+                self.recycle(frame);
+            }
+            return;
+        }
+        while self.pending.len() > self.latency {
+            let available = self.pending.front().is_some_and(|frame| {
+                frame.scopes.iter().all(|scope| match scope.end {
+                    Some(ref end) => end.is_available(),
+                    None => false,
+                })
+            });
+            if !available {
+                break;
+            }
+            let frame = self.pending.pop_front().unwrap();
+            for scope in frame.scopes.iter() {
+                let start = scope.begin.result();
+                let end = scope.end.as_ref().map_or(start, |end| end.result());
+                self.timings.push(ScopeTiming {
+                    name: scope.name.clone(),
+                    frame: frame.index,
+                    depth: scope.depth,
+                    start,
+                    duration: end.saturating_sub(start),
+                });
+            }
+            self.recycle(frame);
+        }
+    }
+
+    fn recycle(&mut self, frame: PendingFrame<Q>) {
+        for scope in frame.scopes {
+            self.pool.push(scope.begin);
+            if let Some(end) = scope.end {
+                self.pool.push(end);
+            }
+        }
+    }
+}
+
+/// Frame profiler which measures nested scopes with GPU timestamps.
+///
+/// The results are collected `latency` frames later when the queries are
+/// available, so the profiler never stalls the pipeline.
+#[derive(Debug)]
+pub struct GpuProfiler {
+    current: PendingFrame<Query>,
+    stack: Vec<usize>,
+    queue: FrameQueue<Query>,
+}
+
+impl GpuProfiler {
+    /// Create a profiler which reads the results `latency` frames later.
+    pub fn new(latency: usize) -> Self {
+        // Clear the disjoint flag before the first timer query.
+        Query::is_disjoint();
+        Self {
+            current: PendingFrame::new(0),
+            stack: Vec::new(),
+            queue: FrameQueue::new(latency),
+        }
+    }
+
+    /// Begin a scope named `name` nested in the current scope.
+    pub fn begin_scope<S: Into<String>>(&mut self, name: S) {
+        let begin = self.new_timestamp();
+        begin.record_timestamp();
+        self.stack.push(self.current.scopes.len());
+        self.current.scopes.push(PendingScope {
+            name: name.into(),
+            depth: self.stack.len() - 1,
+            begin,
+            end: None,
+        });
+    }
+
+    /// End the innermost scope.
+    pub fn end_scope(&mut self) {
+        if let Some(index) = self.stack.pop() {
+            let end = self.new_timestamp();
+            end.record_timestamp();
+            self.current.scopes[index].end = Some(end);
+        }
+    }
+
+    /// Begin a scope which ends when the returned guard dropped.
+    ///
+    /// The guard dereferences to the profiler, so the nested scopes can be
+    /// created from the guard.
+    pub fn scope<S: Into<String>>(&mut self, name: S) -> ProfilerScope<'_> {
+        self.begin_scope(name);
+        ProfilerScope { profiler: self }
+    }
+
+    /// Finish the current frame and collect the results of the previous frames.
+    pub fn end_frame(&mut self) {
+        while !self.stack.is_empty() {
+            self.end_scope();
+        }
+        let index = self.current.index;
+        let frame = std::mem::replace(&mut self.current, PendingFrame::new(index + 1));
+        self.queue.push(frame, Query::is_disjoint());
+    }
+
+    /// Returns the collected timings.
+    pub fn timings(&self) -> &[ScopeTiming] {
+        &self.queue.timings
+    }
+
+    /// Take the collected timings out of the profiler.
+    pub fn take_timings(&mut self) -> Vec<ScopeTiming> {
+        std::mem::take(&mut self.queue.timings)
+    }
+
+    /// Export the collected timings in Chrome trace event format, which can be
+    /// loaded by `chrome://tracing` or Perfetto.
+    pub fn to_chrome_trace(&self) -> String {
+        to_chrome_trace(&self.queue.timings)
+    }
+
+    fn new_timestamp(&mut self) -> Query {
+        self.queue
+            .pool
+            .pop()
+            .unwrap_or_else(|| Query::new(QueryTarget::Timestamp))
+    }
+}
+
+impl Default for GpuProfiler {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// Guard of a profiler scope, ends the scope when dropped.
+#[derive(Debug)]
+pub struct ProfilerScope<'a> {
+    profiler: &'a mut GpuProfiler,
+}
+
+impl<'a> Deref for ProfilerScope<'a> {
+    type Target = GpuProfiler;
+
+    fn deref(&self) -> &Self::Target {
+        self.profiler
+    }
+}
+
+impl<'a> DerefMut for ProfilerScope<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.profiler
+    }
+}
+
+impl<'a> Drop for ProfilerScope<'a> {
+    fn drop(&mut self) {
+        self.profiler.end_scope();
+    }
+}
+
+/// Format `timings` as Chrome trace event JSON, times are in microseconds.
+pub fn to_chrome_trace(timings: &[ScopeTiming]) -> String {
+    let mut json = String::from("{\"traceEvents\":[");
+    for (i, timing) in timings.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        let _ = write!(
+            json,
+            "{{\"name\":\"{}\",\"cat\":\"gpu\",\"ph\":\"X\",\"pid\":0,\"tid\":0,\
+             \"ts\":{:.3},\"dur\":{:.3},\"args\":{{\"frame\":{},\"depth\":{}}}}}",
+            escape_json(&timing.name),
+            timing.start as f64 / 1000.0,
+            timing.duration as f64 / 1000.0,
+            timing.frame,
+            timing.depth
+        );
+    }
+    json.push_str("],\"displayTimeUnit\":\"ns\"}");
+    json
+}
+
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chrome_trace() {
+        let timings = vec![
+            ScopeTiming {
+                name: "shadow \"pass\"".to_string(),
+                frame: 7,
+                depth: 0,
+                start: 1_000_000,
+                duration: 2_500,
+            },
+            ScopeTiming {
+                name: "blur".to_string(),
+                frame: 7,
+                depth: 1,
+                start: 1_001_000,
+                duration: 500,
+            },
+        ];
+        assert_eq!(
+            to_chrome_trace(&timings),
+            "{\"traceEvents\":[\
+             {\"name\":\"shadow \\\"pass\\\"\",\"cat\":\"gpu\",\"ph\":\"X\",\"pid\":0,\"tid\":0,\
+             \"ts\":1000.000,\"dur\":2.500,\"args\":{\"frame\":7,\"depth\":0}},\
+             {\"name\":\"blur\",\"cat\":\"gpu\",\"ph\":\"X\",\"pid\":0,\"tid\":0,\
+             \"ts\":1001.000,\"dur\":0.500,\"args\":{\"frame\":7,\"depth\":1}}\
+             ],\"displayTimeUnit\":\"ns\"}"
+        );
+        assert_eq!(
+            to_chrome_trace(&[]),
+            "{\"traceEvents\":[],\"displayTimeUnit\":\"ns\"}"
+        );
+    }
+
+    /// Fake timestamp available once the shared clock reaches it.
+    struct FakeStamp {
+        time: u64,
+        clock: std::rc::Rc<std::cell::Cell<u64>>,
+    }
+
+    impl Timestamp for FakeStamp {
+        fn is_available(&self) -> bool {
+            self.clock.get() >= self.time
+        }
+
+        fn result(&self) -> u64 {
+            self.time
+        }
+    }
+
+    #[test]
+    fn test_frame_latency() {
+        let clock = std::rc::Rc::new(std::cell::Cell::new(0));
+        let stamp = |time| FakeStamp {
+            time,
+            clock: clock.clone(),
+        };
+        let frame = |index: u64| {
+            let start = index * 100;
+            let mut frame = PendingFrame::new(index);
+            frame.scopes.push(PendingScope {
+                name: "frame".to_string(),
+                depth: 0,
+                begin: stamp(start),
+                end: Some(stamp(start + 50)),
+            });
+            frame
+        };
+        let mut queue = FrameQueue::new(2);
+        queue.push(frame(0), false);
+        queue.push(frame(1), false);
+        // Within the latency, nothing is collected even if available.
+        clock.set(1000);
+        assert!(queue.timings.is_empty());
+        queue.push(frame(2), false);
+        assert_eq!(queue.timings.len(), 1);
+        assert_eq!(queue.timings[0].frame, 0);
+        assert_eq!(queue.timings[0].duration, 50);
+        // Both queries of the frame are recycled.
+        assert_eq!(queue.pool.len(), 2);
+
+        // A frame whose end is not available blocks the later frames.
+        clock.set(120);
+        queue.push(frame(3), false);
+        assert_eq!(queue.pending.len(), 3);
+        clock.set(1000);
+        queue.push(frame(4), false);
+        let frames: Vec<u64> = queue.timings.iter().map(|t| t.frame).collect();
+        assert_eq!(frames, vec![0, 1, 2]);
+        assert_eq!(queue.pending.len(), 2);
+
+        // A disjoint timer drops all pending frames.
+        queue.push(frame(5), true);
+        assert!(queue.pending.is_empty());
+        assert_eq!(queue.timings.len(), 3);
+        assert_eq!(queue.pool.len(), 12);
+    }
+}
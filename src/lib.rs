@@ -10,7 +10,7 @@ pub mod gl;
 pub use gl::GLeglImageOES;
 pub use gl::{
     load_with, GLbitfield, GLboolean, GLchar, GLenum, GLfloat, GLint, GLintptr, GLsizei,
-    GLsizeiptr, GLubyte, GLuint, GLvoid,
+    GLsizeiptr, GLsync, GLubyte, GLuint, GLuint64, GLvoid,
 };
 
 pub mod apis;
@@ -18,6 +18,8 @@ pub use apis::*;
 
 /// Error Object for OpenGL.
 pub mod error;
+#[cfg(feature = "checked")]
+pub use error::set_panic_on_error;
 pub use error::Error;
 
 pub mod auto_binder;
@@ -29,21 +31,98 @@ pub use buffer::*;
 pub mod clear_buffers;
 pub use clear_buffers::*;
 
+pub mod cube_map;
+pub use cube_map::*;
+
+pub mod debug;
+pub use debug::*;
+
+#[cfg(all(
+    unix,
+    feature = "egls",
+    any(feature = "gles1", feature = "gles2", feature = "gles3")
+))]
+pub mod dma_buf;
+#[cfg(all(
+    unix,
+    feature = "egls",
+    any(feature = "gles1", feature = "gles2", feature = "gles3")
+))]
+pub use dma_buf::*;
+
+#[cfg(all(
+    unix,
+    feature = "egls",
+    any(feature = "gles1", feature = "gles2", feature = "gles3")
+))]
+pub mod egl_image;
+#[cfg(all(
+    unix,
+    feature = "egls",
+    any(feature = "gles1", feature = "gles2", feature = "gles3")
+))]
+pub use egl_image::*;
+
+pub mod fence;
+pub use fence::*;
+
 pub mod framebuffer;
 pub use framebuffer::*;
 
+pub mod gpu_profiler;
+pub use gpu_profiler::*;
+
+pub mod occlusion;
+pub use occlusion::*;
+
+pub mod pixel_buffer;
+pub use pixel_buffer::*;
+
+pub mod pixel_store;
+pub use pixel_store::*;
+
+pub mod query;
+pub use query::*;
+
+pub mod render_state;
+pub use render_state::*;
+
+pub mod renderbuffer;
+pub use renderbuffer::*;
+
+pub mod sampler;
+pub use sampler::*;
+
+pub mod scissor;
+pub use scissor::*;
+
 pub mod shader;
 pub use shader::*;
 
+pub mod shadow_map;
+pub use shadow_map::*;
+
 /// The shared library helper.
 pub mod so;
 
+pub mod state_cache;
+pub use state_cache::*;
+
+pub mod stream_buffer;
+pub use stream_buffer::*;
+
 pub mod texture;
 pub use texture::*;
 
+pub mod transform_feedback;
+pub use transform_feedback::*;
+
 pub mod variant;
 pub use variant::*;
 
+pub mod video_frame;
+pub use video_frame::*;
+
 pub mod viewport;
 pub use viewport::*;
 
@@ -53,6 +132,7 @@ pub type Finalizer<'a, T> = Box<dyn Fn(&T) + 'a>;
 pub type Isometry3 = na::Isometry3<GLfloat>;
 pub type Orthographic3 = na::Orthographic3<GLfloat>;
 pub type Perspective3 = na::Perspective3<GLfloat>;
+pub type Point3 = na::Point3<GLfloat>;
 pub type Matrix4 = na::Matrix4<GLfloat>;
 pub type Transform3 = na::Transform3<GLfloat>;
 pub type Vector2 = na::Vector2<GLfloat>;
@@ -0,0 +1,321 @@
+use crate::{
+    gl, prelude::*, AutoBinder, Buffer, ColorMask, DepthState, GLenum, GLfloat, GLint, GLuint,
+    Matrix4, Program, Query, QueryTarget, StateCache, Vector3, VertexArray, VertexAttrib,
+};
+use std::cell::Cell;
+
+/// Progress of an occlusion query across the frames.
+#[derive(Debug)]
+struct QueryState {
+    pending: Cell<bool>,
+    active: Cell<bool>,
+    visible: Cell<bool>,
+}
+
+impl QueryState {
+    fn new() -> Self {
+        Self {
+            pending: Cell::new(false),
+            active: Cell::new(false),
+            visible: Cell::new(true),
+        }
+    }
+
+    /// Receive the `result` of the pending query if available.
+    fn receive(&self, result: Option<u64>) {
+        if let Some(samples) = result {
+            self.visible.set(samples != 0);
+            self.pending.set(false);
+        }
+    }
+
+    /// Returns true if the query should begin, a query whose result is not
+    /// read yet is not restarted.
+    fn begin(&self) -> bool {
+        self.active.set(!self.pending.get());
+        self.active.get()
+    }
+
+    /// Returns true if the query began and should end.
+    fn end(&self) -> bool {
+        let active = self.active.replace(false);
+        if active {
+            self.pending.set(true);
+        }
+        active
+    }
+}
+
+/// Occlusion query which tells whether any samples of the draws between bind
+/// and unbind passed the depth and stencil tests.
+///
+/// The result is read without stalling, so `is_visible()` reports the result
+/// of a previous frame. The query is not restarted until the result of the
+/// previous one is read, so the draws bound while the result is pending are
+/// not counted.
+#[derive(Debug)]
+pub struct OcclusionQuery {
+    query: Query,
+    state: QueryState,
+}
+
+impl OcclusionQuery {
+    /// Create a query with `GL_ANY_SAMPLES_PASSED`.
+    pub fn new() -> Self {
+        Self::with_target(QueryTarget::AnySamplesPassed)
+    }
+
+    /// Create a query with `GL_ANY_SAMPLES_PASSED_CONSERVATIVE`, which is
+    /// faster but may report false positives.
+    pub fn new_conservative() -> Self {
+        Self::with_target(QueryTarget::AnySamplesPassedConservative)
+    }
+
+    fn with_target(target: QueryTarget) -> Self {
+        Self {
+            query: Query::new(target),
+            state: QueryState::new(),
+        }
+    }
+
+    /// Returns the underlying Query.
+    pub fn query(&self) -> &Query {
+        &self.query
+    }
+
+    /// Returns `true` if the latest available result reported any samples
+    /// passed, `true` before the first result is available.
+    pub fn is_visible(&self) -> bool {
+        self.poll();
+        self.state.visible.get()
+    }
+
+    /// Returns `true` if the result of the latest query is not available yet.
+    pub fn is_pending(&self) -> bool {
+        self.poll();
+        self.state.pending.get()
+    }
+
+    /// Returns a Bindable which discards the draws between bind and unbind if
+    /// the query reported no samples passed, evaluated by the GPU.
+    ///
+    /// `wait` makes the GPU wait for the result instead of drawing anyway.
+    #[cfg(any(feature = "gl3", feature = "gl4"))]
+    pub fn conditional_render(&self, wait: bool) -> ConditionalRender<'_> {
+        ConditionalRender { query: self, wait }
+    }
+
+    fn poll(&self) {
+        if self.state.pending.get() {
+            self.state.receive(self.query.try_result());
+        }
+    }
+}
+
+impl Default for OcclusionQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bindable for OcclusionQuery {
+    fn bind(&self) {
+        self.poll();
+        if self.state.begin() {
+            self.query.begin();
+        }
+    }
+
+    fn unbind(&self) {
+        if self.state.end() {
+            self.query.end();
+        }
+    }
+}
+
+/// Conditional rendering scope of an OcclusionQuery.
+#[cfg(any(feature = "gl3", feature = "gl4"))]
+#[derive(Debug)]
+pub struct ConditionalRender<'a> {
+    query: &'a OcclusionQuery,
+    wait: bool,
+}
+
+#[cfg(any(feature = "gl3", feature = "gl4"))]
+impl<'a> Bindable for ConditionalRender<'a> {
+    fn bind(&self) {
+        let mode = if self.wait {
+            gl::QUERY_WAIT
+        } else {
+            gl::QUERY_NO_WAIT
+        };
+        crate::begin_conditional_render(self.query.query().id(), mode);
+    }
+
+    fn unbind(&self) {
+        crate::end_conditional_render();
+    }
+}
+
+const PROXY_VERT: &str = "#version 100
+attribute vec3 a_position;
+uniform mat4 u_mvp;
+
+void main()
+{
+    gl_Position = u_mvp * vec4(a_position, 1.0);
+}
+";
+
+const PROXY_FRAG: &str = "#version 100
+precision mediump float;
+
+void main()
+{
+    gl_FragColor = vec4(1.0);
+}
+";
+
+/// Occlusion culling with proxy bounding boxes.
+///
+/// The bounding box of an object is drawn invisibly inside an OcclusionQuery,
+/// and the object itself is drawn only if the box was visible in the previous
+/// frame. Draw the occluders first, front to back.
+#[derive(Debug)]
+pub struct OcclusionCuller {
+    program: Program,
+    mvp_location: GLint,
+    vao: VertexArray,
+    _vbo: Buffer,
+    _ibo: Buffer,
+}
+
+impl OcclusionCuller {
+    pub fn new() -> Result<Self, String> {
+        let program = Program::from_sources(&[
+            (PROXY_VERT, gl::VERTEX_SHADER),
+            (PROXY_FRAG, gl::FRAGMENT_SHADER),
+        ])?;
+        let position = program
+            .locate_attrib("a_position")
+            .map_err(|e| e.to_string())?;
+        let mvp_location = program.locate_uniform("u_mvp").map_err(|e| e.to_string())?;
+
+        // Corners of the unit cube, the bits of the index are the x, y and z.
+        let vertices: Vec<GLfloat> = (0..8)
+            .flat_map(|i| {
+                vec![
+                    (i & 1) as GLfloat,
+                    (i >> 1 & 1) as GLfloat,
+                    (i >> 2) as GLfloat,
+                ]
+            })
+            .collect();
+        let indices: [u8; 36] = [
+            0, 2, 4, 2, 6, 4, // -X
+            1, 5, 3, 3, 5, 7, // +X
+            0, 4, 1, 1, 4, 5, // -Y
+            2, 3, 6, 3, 7, 6, // +Y
+            0, 1, 2, 1, 3, 2, // -Z
+            4, 6, 5, 5, 6, 7, // +Z
+        ];
+        let vao = VertexArray::new();
+        vao.bind();
+        let vbo = Buffer::new_array();
+        vbo.static_draw_data(&vertices[..]);
+        let ibo = Buffer::new_element_array();
+        ibo.static_draw_data(&indices[..]);
+        vao.enable_attrib(&VertexAttrib::new(
+            position as GLuint,
+            3,
+            gl::FLOAT,
+            gl::FALSE,
+            (3 * std::mem::size_of::<GLfloat>()) as GLint,
+            0,
+        ));
+        vao.unbind();
+        vbo.unbind();
+        ibo.unbind();
+
+        Ok(Self {
+            program,
+            mvp_location,
+            vao,
+            _vbo: vbo,
+            _ibo: ibo,
+        })
+    }
+
+    /// Draw the box from `min` to `max` transformed by `mvp` into `query`,
+    /// without writing color and depth.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn draw_proxy(&self, query: &OcclusionQuery, mvp: &Matrix4, min: &Vector3, max: &Vector3) {
+        let model = Matrix4::new_translation(min) * Matrix4::new_nonuniform_scaling(&(max - min));
+        let cull_face = StateCache::with(|cache| cache.is_enabled(gl::CULL_FACE));
+        StateCache::with(|cache| cache.set_enabled(gl::CULL_FACE, false));
+        // The previous masks are restored when the binder dropped.
+        let depth = DepthState {
+            write: false,
+            ..DepthState::current()
+        };
+        let color = ColorMask::none();
+        {
+            let _a = AutoBinder::new(vec![&depth, &color, &self.program, &self.vao, query]);
+            crate::uniform_matrix4fv(self.mvp_location, gl::FALSE, (mvp * model).as_slice());
+            crate::draw_elements(gl::TRIANGLES, 36, gl::UNSIGNED_BYTE as GLenum, 0);
+        }
+        StateCache::with(|cache| cache.set_enabled(gl::CULL_FACE, cull_face));
+    }
+
+    /// Draw the proxy box into `query`, then call `draw` only if the box was
+    /// visible in the latest result, returns whether `draw` is called.
+    ///
+    /// The proxy is skipped while the result of the query is pending.
+    pub fn draw_if_visible<F>(
+        &self,
+        query: &OcclusionQuery,
+        mvp: &Matrix4,
+        min: &Vector3,
+        max: &Vector3,
+        draw: F,
+    ) -> bool
+    where
+        F: FnOnce(),
+    {
+        let visible = query.is_visible();
+        if !query.is_pending() {
+            self.draw_proxy(query, mvp, min, max);
+        }
+        if visible {
+            draw();
+        }
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lagging_results() {
+        let state = QueryState::new();
+        assert!(state.begin());
+        assert!(state.end());
+        // The result lags two frames, the query is not restarted meanwhile.
+        for _ in 0..2 {
+            state.receive(None);
+            assert!(!state.begin());
+            assert!(!state.end());
+            assert!(state.visible.get());
+        }
+        state.receive(Some(0));
+        assert!(!state.visible.get());
+        assert!(!state.pending.get());
+        // The next frame culls the draw and restarts the query.
+        assert!(state.begin());
+        assert!(state.end());
+        state.receive(Some(1));
+        assert!(state.visible.get());
+    }
+}
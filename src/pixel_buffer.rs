@@ -0,0 +1,233 @@
+use crate::{
+    gl, prelude::*, texture::unpack_size, Buffer, GLenum, GLint, GLsizei, GLsizeiptr, MapAccess,
+    PixelUnpack, StateCache, Texture, TextureFormat, TextureTexel,
+};
+
+/// Asynchronous texture uploader which round-robins pixel unpack buffers.
+///
+/// Pixels are copied into a buffer object and transferred to the texture by
+/// the GPU, so the upload does not wait for the texture to be idle.
+#[derive(Debug)]
+pub struct PixelUploader {
+    buffers: Vec<Buffer>,
+    index: usize,
+    size: usize,
+}
+
+impl PixelUploader {
+    /// Create an uploader with `count` buffers of `size` bytes each.
+    pub fn new(count: usize, size: usize) -> Self {
+        let buffers = (0..count.max(1))
+            .map(|_| {
+                let buffer = Buffer::new(gl::PIXEL_UNPACK_BUFFER);
+                crate::buffer_data::<u8>(
+                    gl::PIXEL_UNPACK_BUFFER,
+                    size as GLsizeiptr,
+                    None,
+                    gl::STREAM_DRAW,
+                );
+                buffer
+            })
+            .collect();
+        StateCache::with(|cache| cache.bind_buffer(gl::PIXEL_UNPACK_BUFFER, 0));
+        Self {
+            buffers,
+            index: 0,
+            size,
+        }
+    }
+
+    /// Returns the size in bytes of each buffer.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Upload `bytes` to the whole `texture`.
+    /// # Note
+    /// The texture must be binded before call the routine.
+    pub fn upload(
+        &mut self,
+        texture: &Texture<'_>,
+        format: TextureFormat,
+        texel: TextureTexel,
+        bytes: &[u8],
+    ) -> Result<(), String> {
+        self.upload_region(
+            texture,
+            0,
+            0,
+            texture.width(),
+            texture.height(),
+            format,
+            texel,
+            bytes,
+        )
+    }
+
+    /// Upload `bytes` to the region at (`x`, `y`) with size `width` x `height`.
+    /// # Note
+    /// The texture must be binded before call the routine.
+    #[allow(clippy::too_many_arguments)]
+    pub fn upload_region(
+        &mut self,
+        texture: &Texture<'_>,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        format: TextureFormat,
+        texel: TextureTexel,
+        bytes: &[u8],
+    ) -> Result<(), String> {
+        // The transfer reads the buffer with the current unpack parameters.
+        let size = unpack_size(&PixelUnpack::current(), width, height, 1, format, texel);
+        if bytes.len() < size {
+            return Err(format!(
+                "Region requires {} bytes, but got {}!",
+                size,
+                bytes.len()
+            ));
+        }
+        if size > self.size {
+            return Err(format!(
+                "Pixel buffer holds {} bytes, but region requires {}!",
+                self.size, size
+            ));
+        }
+        let buffer = &self.buffers[self.index];
+        self.index = (self.index + 1) % self.buffers.len();
+        // Invalidating the buffer orphans the storage still read by the
+        // pending transfer, and the pixels are written without an extra copy.
+        let mut mapped =
+            buffer.map_range::<u8>(0, size, MapAccess::WRITE | MapAccess::INVALIDATE_BUFFER)?;
+        mapped.write(0, &bytes[..size])?;
+        mapped.unmap()?;
+        buffer.bind();
+        let result = texture.update_region_from_buffer(x, y, width, height, format, texel, 0);
+        buffer.unbind();
+        result
+    }
+}
+
+/// Asynchronous pixel reader which reads the framebuffer into pixel pack
+/// buffers and maps them frames later.
+///
+/// With `count` buffers, the pixels delivered by `read()` are from the read
+/// `count` calls before, so the GPU has finished the transfer and mapping
+/// never stalls.
+#[derive(Debug)]
+pub struct PixelReader {
+    buffers: Vec<Buffer>,
+    pending: Vec<bool>,
+    index: usize,
+    width: usize,
+    height: usize,
+    format: TextureFormat,
+    texel: TextureTexel,
+}
+
+impl PixelReader {
+    /// Create a reader with `count` buffers for `width` x `height` pixels.
+    pub fn new(
+        count: usize,
+        width: usize,
+        height: usize,
+        format: TextureFormat,
+        texel: TextureTexel,
+    ) -> Self {
+        let count = count.max(2);
+        let size = width * height * format.pixel_size(texel);
+        let buffers = (0..count)
+            .map(|_| {
+                let buffer = Buffer::new(gl::PIXEL_PACK_BUFFER);
+                crate::buffer_data::<u8>(
+                    gl::PIXEL_PACK_BUFFER,
+                    size as GLsizeiptr,
+                    None,
+                    gl::STREAM_READ,
+                );
+                buffer
+            })
+            .collect();
+        StateCache::with(|cache| cache.bind_buffer(gl::PIXEL_PACK_BUFFER, 0));
+        Self {
+            buffers,
+            pending: vec![false; count],
+            index: 0,
+            width,
+            height,
+            format,
+            texel,
+        }
+    }
+
+    /// Returns the size in bytes of the pixels of one read.
+    pub fn size(&self) -> usize {
+        self.width * self.height * self.format.pixel_size(self.texel)
+    }
+
+    /// Read pixels at (`x`, `y`) from the binded read framebuffer.
+    ///
+    /// The pixels of the oldest pending read are passed to `f` before its
+    /// buffer is reused, returns `true` if `f` has been called.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn read<F>(&mut self, x: i32, y: i32, f: F) -> bool
+    where
+        F: FnOnce(&[u8]),
+    {
+        let index = self.index;
+        self.index = (self.index + 1) % self.buffers.len();
+        let buffer = &self.buffers[index];
+        let delivered = self.pending[index] && Self::map(buffer, self.size(), f);
+        buffer.bind();
+        let mut alignment: [GLint; 1] = [4];
+        crate::get_integerv(gl::PACK_ALIGNMENT, &mut alignment);
+        crate::pixel_storei(gl::PACK_ALIGNMENT, 1);
+        crate::read_pixels_offset(
+            x as GLint,
+            y as GLint,
+            self.width as GLsizei,
+            self.height as GLsizei,
+            self.format as GLenum,
+            self.texel as GLenum,
+            0,
+        );
+        crate::pixel_storei(gl::PACK_ALIGNMENT, alignment[0]);
+        buffer.unbind();
+        self.pending[index] = true;
+        delivered
+    }
+
+    /// Wait and pass the pixels of all pending reads to `f` from the oldest.
+    pub fn flush<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let count = self.buffers.len();
+        for i in 0..count {
+            let index = (self.index + i) % count;
+            if self.pending[index] {
+                Self::map(&self.buffers[index], self.size(), &mut f);
+                self.buffers[index].unbind();
+                self.pending[index] = false;
+            }
+        }
+    }
+
+    /// Map the pixel pack `buffer` and pass the contents to `f`.
+    fn map<F>(buffer: &Buffer, size: usize, f: F) -> bool
+    where
+        F: FnOnce(&[u8]),
+    {
+        match buffer.map_range::<u8>(0, size, MapAccess::READ) {
+            Ok(mapped) => match mapped.as_slice() {
+                Some(data) => {
+                    f(data);
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{gl, prelude::*, GLint};
+use std::cell::RefCell;
+
+thread_local! {
+    static SAVED_PIXEL_UNPACKS: RefCell<Vec<PixelUnpack>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pixel unpack parameters used when uploading texture data.
+///
+/// Binding the object applies the parameters, unbinding restores the previous ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PixelUnpack {
+    /// Alignment of the start of each row in bytes, one of 1, 2, 4 or 8.
+    pub alignment: GLint,
+    /// Number of pixels in a row, 0 means same as the width of the upload.
+    pub row_length: GLint,
+    /// Number of pixels skipped at the start of each row.
+    pub skip_pixels: GLint,
+    /// Number of rows skipped at the start of the data.
+    pub skip_rows: GLint,
+}
+
+impl PixelUnpack {
+    pub fn new() -> Self {
+        Self {
+            alignment: 4,
+            row_length: 0,
+            skip_pixels: 0,
+            skip_rows: 0,
+        }
+    }
+
+    /// Construct parameters for rows of `stride` bytes with `pixel_size` bytes per pixel,
+    /// the `stride` must be a multiple of the `pixel_size`.
+    pub fn with_stride(stride: usize, pixel_size: usize) -> Result<Self, String> {
+        if pixel_size == 0 || !stride.is_multiple_of(pixel_size) {
+            return Err(format!(
+                "Stride {} is not a multiple of the pixel size {}!",
+                stride, pixel_size
+            ));
+        }
+        Ok(Self {
+            alignment: 1,
+            row_length: (stride / pixel_size) as GLint,
+            ..Self::new()
+        })
+    }
+
+    pub fn with_alignment(mut self, alignment: GLint) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn with_row_length(mut self, row_length: GLint) -> Self {
+        self.row_length = row_length;
+        self
+    }
+
+    pub fn with_skip_pixels(mut self, skip_pixels: GLint) -> Self {
+        self.skip_pixels = skip_pixels;
+        self
+    }
+
+    pub fn with_skip_rows(mut self, skip_rows: GLint) -> Self {
+        self.skip_rows = skip_rows;
+        self
+    }
+
+    /// Returns the parameters currently set in the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn current() -> Self {
+        let get = |pname| {
+            let mut value: [GLint; 1] = [0];
+            crate::get_integerv(pname, &mut value);
+            value[0]
+        };
+        Self {
+            alignment: get(gl::UNPACK_ALIGNMENT),
+            row_length: get(gl::UNPACK_ROW_LENGTH),
+            skip_pixels: get(gl::UNPACK_SKIP_PIXELS),
+            skip_rows: get(gl::UNPACK_SKIP_ROWS),
+        }
+    }
+
+    /// Apply the parameters to the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn apply(&self) {
+        crate::pixel_storei(gl::UNPACK_ALIGNMENT, self.alignment);
+        crate::pixel_storei(gl::UNPACK_ROW_LENGTH, self.row_length);
+        crate::pixel_storei(gl::UNPACK_SKIP_PIXELS, self.skip_pixels);
+        crate::pixel_storei(gl::UNPACK_SKIP_ROWS, self.skip_rows);
+    }
+
+    /// Returns the distance in bytes between the starts of the rows when
+    /// uploading rows of `width` pixels.
+    pub fn row_stride(&self, width: usize, pixel_size: usize) -> usize {
+        let row_length = match self.row_length {
+            0 => width,
+            v => v as usize,
+        };
+        let alignment = self.alignment.max(1) as usize;
+        (row_length * pixel_size).div_ceil(alignment) * alignment
+    }
+
+    /// Returns the number of bytes read when uploading `width` x `height` pixels.
+    pub fn required_size(&self, width: usize, height: usize, pixel_size: usize) -> usize {
+        if width == 0 || height == 0 {
+            return 0;
+        }
+        let stride = self.row_stride(width, pixel_size);
+        let skip = self.skip_rows as usize * stride + self.skip_pixels as usize * pixel_size;
+        skip + (height - 1) * stride + width * pixel_size
+    }
+
+    /// Returns the number of bytes read when uploading `depth` images of
+    /// `width` x `height` pixels, the images are expected to be `height` rows
+    /// apart as with the default `GL_UNPACK_IMAGE_HEIGHT`.
+    pub fn required_size_3d(
+        &self,
+        width: usize,
+        height: usize,
+        depth: usize,
+        pixel_size: usize,
+    ) -> usize {
+        if depth == 0 {
+            return 0;
+        }
+        (depth - 1) * height * self.row_stride(width, pixel_size)
+            + self.required_size(width, height, pixel_size)
+    }
+}
+
+impl Default for PixelUnpack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bindable for PixelUnpack {
+    fn bind(&self) {
+        let previous = PixelUnpack::current();
+        SAVED_PIXEL_UNPACKS.with(|saved| saved.borrow_mut().push(previous));
+        self.apply();
+    }
+
+    fn unbind(&self) {
+        if let Some(previous) = SAVED_PIXEL_UNPACKS.with(|saved| saved.borrow_mut().pop()) {
+            previous.apply();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_size() {
+        assert_eq!(PixelUnpack::new().required_size(3, 2, 3), 12 + 9);
+        assert_eq!(
+            PixelUnpack::with_stride(64, 4)
+                .unwrap()
+                .required_size(8, 2, 4),
+            64 + 32
+        );
+        let unpack = PixelUnpack::with_stride(64, 4)
+            .unwrap()
+            .with_skip_pixels(2)
+            .with_skip_rows(1);
+        assert_eq!(unpack.required_size(8, 2, 4), 64 + 8 + 64 + 32);
+        assert_eq!(
+            PixelUnpack::new().required_size_3d(3, 2, 2, 3),
+            2 * 12 + 12 + 9
+        );
+    }
+
+    #[test]
+    fn test_with_stride() {
+        assert_eq!(PixelUnpack::with_stride(30, 3).unwrap().row_length, 10);
+        assert!(PixelUnpack::with_stride(30, 4).is_err());
+        assert!(PixelUnpack::with_stride(30, 0).is_err());
+    }
+}
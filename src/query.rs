@@ -0,0 +1,163 @@
+use crate::{gl, prelude::*, GLenum, GLuint};
+
+#[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+const TIME_ELAPSED: GLenum = gl::TIME_ELAPSED_EXT;
+#[cfg(not(any(feature = "gles1", feature = "gles2", feature = "gles3")))]
+const TIME_ELAPSED: GLenum = gl::TIME_ELAPSED;
+#[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+const TIMESTAMP: GLenum = gl::TIMESTAMP_EXT;
+#[cfg(not(any(feature = "gles1", feature = "gles2", feature = "gles3")))]
+const TIMESTAMP: GLenum = gl::TIMESTAMP;
+
+/// Targets of the query objects.
+///
+/// # Note
+/// Timer queries require `GL_EXT_disjoint_timer_query` on OpenGL ES.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryTarget {
+    /// Nanoseconds elapsed between begin and end of the query.
+    TimeElapsed = TIME_ELAPSED as isize,
+    /// GPU time in nanoseconds when all previous commands completed.
+    Timestamp = TIMESTAMP as isize,
+    /// Whether any samples passed the depth and stencil tests.
+    AnySamplesPassed = gl::ANY_SAMPLES_PASSED as isize,
+    /// Whether any samples passed, the implementation may be less precise
+    /// and report false positives to be faster.
+    AnySamplesPassedConservative = gl::ANY_SAMPLES_PASSED_CONSERVATIVE as isize,
+    /// Number of primitives generated by the vertex processing.
+    PrimitivesGenerated = gl::PRIMITIVES_GENERATED as isize,
+    /// Number of primitives written into the transform feedback buffers.
+    TransformFeedbackPrimitivesWritten = gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN as isize,
+}
+
+/// Query object, binding the object begins the query and unbinding ends it.
+///
+/// Binding a `Timestamp` query records the timestamp instead, and unbinding
+/// does nothing.
+#[derive(Debug)]
+pub struct Query {
+    id: GLuint,
+    target: QueryTarget,
+}
+
+impl Query {
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn new(target: QueryTarget) -> Self {
+        Self {
+            id: crate::new_query(),
+            target,
+        }
+    }
+
+    pub fn new_time_elapsed() -> Self {
+        Self::new(QueryTarget::TimeElapsed)
+    }
+
+    pub fn new_timestamp() -> Self {
+        Self::new(QueryTarget::Timestamp)
+    }
+
+    pub fn new_any_samples_passed() -> Self {
+        Self::new(QueryTarget::AnySamplesPassed)
+    }
+
+    pub fn new_primitives_generated() -> Self {
+        Self::new(QueryTarget::PrimitivesGenerated)
+    }
+
+    pub fn new_primitives_written() -> Self {
+        Self::new(QueryTarget::TransformFeedbackPrimitivesWritten)
+    }
+
+    /// Returns the Id of the Query.
+    pub fn id(&self) -> GLuint {
+        self.id
+    }
+
+    /// Returns the Target of the Query.
+    pub fn target(&self) -> QueryTarget {
+        self.target
+    }
+
+    /// Begin the query, not available for `Timestamp` queries.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn begin(&self) {
+        crate::begin_query(self.target as GLenum, self.id);
+    }
+
+    /// End the active query of the target.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn end(&self) {
+        crate::end_query(self.target as GLenum);
+    }
+
+    /// Record the GPU time when all previous commands completed, only for
+    /// `Timestamp` queries.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn record_timestamp(&self) {
+        crate::query_counter(self.id, TIMESTAMP);
+    }
+
+    /// Returns `true` if the result is available, never blocks.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn is_available(&self) -> bool {
+        crate::get_query_objectuiv(self.id, gl::QUERY_RESULT_AVAILABLE) != 0
+    }
+
+    /// Returns the result if available, never blocks.
+    pub fn try_result(&self) -> Option<u64> {
+        if self.is_available() {
+            Some(self.result())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the result, blocks until the result is available.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn result(&self) -> u64 {
+        match self.target {
+            QueryTarget::TimeElapsed | QueryTarget::Timestamp => {
+                crate::get_query_objectui64v(self.id, gl::QUERY_RESULT)
+            }
+            _ => crate::get_query_objectuiv(self.id, gl::QUERY_RESULT) as u64,
+        }
+    }
+
+    /// Returns `true` if the GPU timer has been disjoint since the last check,
+    /// which makes the results of the pending timer queries invalid.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn is_disjoint() -> bool {
+        #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+        {
+            let mut disjoint: [crate::GLint; 1] = [0];
+            crate::get_integerv(gl::GPU_DISJOINT_EXT, &mut disjoint);
+            disjoint[0] != 0
+        }
+        #[cfg(not(any(feature = "gles1", feature = "gles2", feature = "gles3")))]
+        {
+            false
+        }
+    }
+}
+
+impl Drop for Query {
+    fn drop(&mut self) {
+        crate::delete_queries(&[self.id]);
+    }
+}
+
+impl Bindable for Query {
+    fn bind(&self) {
+        match self.target {
+            QueryTarget::Timestamp => self.record_timestamp(),
+            _ => self.begin(),
+        }
+    }
+
+    fn unbind(&self) {
+        if self.target != QueryTarget::Timestamp {
+            self.end();
+        }
+    }
+}
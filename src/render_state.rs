@@ -0,0 +1,668 @@
+use crate::{gl, prelude::*, CompareFunc, GLboolean, GLenum, GLfloat, GLint, GLuint, StateCache};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+/// Define a fieldless enum of GL constants with `from_raw()`.
+macro_rules! gl_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident = $value:path,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant = $value as isize,)*
+        }
+
+        impl $name {
+            /// Returns the variant of the raw `GL_*` value.
+            pub fn from_raw(value: GLint) -> Option<Self> {
+                $(
+                    if value == $value as GLint {
+                        return Some($name::$variant);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+/// Implement Bindable which applies the state on bind and restores the state
+/// queried before on unbind, the saved states are stacked per thread.
+macro_rules! impl_bindable_state {
+    ($name:ident, $saved:ident) => {
+        thread_local! {
+            static $saved: RefCell<Vec<$name>> = RefCell::new(Vec::new());
+        }
+
+        impl Bindable for $name {
+            fn bind(&self) {
+                let previous = $name::current();
+                $saved.with(|saved| saved.borrow_mut().push(previous));
+                self.apply();
+            }
+
+            fn unbind(&self) {
+                if let Some(previous) = $saved.with(|saved| saved.borrow_mut().pop()) {
+                    previous.apply();
+                }
+            }
+        }
+    };
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+fn get_integer(pname: GLenum) -> GLint {
+    let mut value: [GLint; 1] = [0];
+    crate::get_integerv(pname, &mut value);
+    value[0]
+}
+
+#[cfg_attr(feature = "checked", track_caller)]
+fn get_float(pname: GLenum) -> GLfloat {
+    let mut value: [GLfloat; 1] = [0.0];
+    crate::get_floatv(pname, &mut value);
+    value[0]
+}
+
+fn gl_bool(value: bool) -> GLboolean {
+    if value {
+        gl::TRUE
+    } else {
+        gl::FALSE
+    }
+}
+
+fn set_enabled(cap: GLenum, enabled: bool) {
+    StateCache::with(|cache| cache.set_enabled(cap, enabled));
+}
+
+fn is_enabled(cap: GLenum) -> bool {
+    StateCache::with(|cache| cache.is_enabled(cap))
+}
+
+gl_enum! {
+    /// Source and destination factors of the blending.
+    pub enum BlendFactor {
+        Zero = gl::ZERO,
+        One = gl::ONE,
+        SrcColor = gl::SRC_COLOR,
+        OneMinusSrcColor = gl::ONE_MINUS_SRC_COLOR,
+        DstColor = gl::DST_COLOR,
+        OneMinusDstColor = gl::ONE_MINUS_DST_COLOR,
+        SrcAlpha = gl::SRC_ALPHA,
+        OneMinusSrcAlpha = gl::ONE_MINUS_SRC_ALPHA,
+        DstAlpha = gl::DST_ALPHA,
+        OneMinusDstAlpha = gl::ONE_MINUS_DST_ALPHA,
+        ConstantColor = gl::CONSTANT_COLOR,
+        OneMinusConstantColor = gl::ONE_MINUS_CONSTANT_COLOR,
+        ConstantAlpha = gl::CONSTANT_ALPHA,
+        OneMinusConstantAlpha = gl::ONE_MINUS_CONSTANT_ALPHA,
+        SrcAlphaSaturate = gl::SRC_ALPHA_SATURATE,
+    }
+}
+
+gl_enum! {
+    /// Equations which combine the source and destination of the blending.
+    pub enum BlendEquation {
+        Add = gl::FUNC_ADD,
+        Subtract = gl::FUNC_SUBTRACT,
+        ReverseSubtract = gl::FUNC_REVERSE_SUBTRACT,
+        Min = gl::MIN,
+        Max = gl::MAX,
+    }
+}
+
+gl_enum! {
+    /// Actions on the stored stencil value.
+    pub enum StencilOp {
+        Keep = gl::KEEP,
+        Zero = gl::ZERO,
+        Replace = gl::REPLACE,
+        Incr = gl::INCR,
+        IncrWrap = gl::INCR_WRAP,
+        Decr = gl::DECR,
+        DecrWrap = gl::DECR_WRAP,
+        Invert = gl::INVERT,
+    }
+}
+
+gl_enum! {
+    /// Faces which are culled.
+    pub enum CullMode {
+        Front = gl::FRONT,
+        Back = gl::BACK,
+        FrontAndBack = gl::FRONT_AND_BACK,
+    }
+}
+
+gl_enum! {
+    /// Winding of the front faces.
+    #[derive(Default)]
+    pub enum FrontFace {
+        /// Counter-clockwise.
+        #[default]
+        Ccw = gl::CCW,
+        /// Clockwise.
+        Cw = gl::CW,
+    }
+}
+
+/// Blending state.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlendState {
+    pub enabled: bool,
+    pub src_rgb: BlendFactor,
+    pub dst_rgb: BlendFactor,
+    pub src_alpha: BlendFactor,
+    pub dst_alpha: BlendFactor,
+    pub equation_rgb: BlendEquation,
+    pub equation_alpha: BlendEquation,
+    /// Constant color for the `Constant*` factors.
+    pub color: [GLfloat; 4],
+}
+
+impl BlendState {
+    /// Blending disabled.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::with_factors(BlendFactor::One, BlendFactor::Zero)
+        }
+    }
+
+    /// Blending of the non-premultiplied alpha.
+    pub fn alpha() -> Self {
+        Self {
+            src_alpha: BlendFactor::One,
+            ..Self::with_factors(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha)
+        }
+    }
+
+    /// Blending of the premultiplied alpha.
+    pub fn premultiplied() -> Self {
+        Self::with_factors(BlendFactor::One, BlendFactor::OneMinusSrcAlpha)
+    }
+
+    /// Additive blending.
+    pub fn additive() -> Self {
+        Self::with_factors(BlendFactor::One, BlendFactor::One)
+    }
+
+    /// Blending enabled with same factors for RGB and alpha, and `Add` equation.
+    pub fn with_factors(src: BlendFactor, dst: BlendFactor) -> Self {
+        Self {
+            enabled: true,
+            src_rgb: src,
+            dst_rgb: dst,
+            src_alpha: src,
+            dst_alpha: dst,
+            equation_rgb: BlendEquation::Add,
+            equation_alpha: BlendEquation::Add,
+            color: [0.0; 4],
+        }
+    }
+
+    pub fn with_equation(mut self, equation: BlendEquation) -> Self {
+        self.equation_rgb = equation;
+        self.equation_alpha = equation;
+        self
+    }
+
+    pub fn with_color(mut self, color: [GLfloat; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Query the current state of the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn current() -> Self {
+        let mut color: [GLfloat; 4] = [0.0; 4];
+        crate::get_floatv(gl::BLEND_COLOR, &mut color);
+        let factor = |pname, default| BlendFactor::from_raw(get_integer(pname)).unwrap_or(default);
+        let equation =
+            |pname| BlendEquation::from_raw(get_integer(pname)).unwrap_or(BlendEquation::Add);
+        Self {
+            enabled: is_enabled(gl::BLEND),
+            src_rgb: factor(gl::BLEND_SRC_RGB, BlendFactor::One),
+            dst_rgb: factor(gl::BLEND_DST_RGB, BlendFactor::Zero),
+            src_alpha: factor(gl::BLEND_SRC_ALPHA, BlendFactor::One),
+            dst_alpha: factor(gl::BLEND_DST_ALPHA, BlendFactor::Zero),
+            equation_rgb: equation(gl::BLEND_EQUATION_RGB),
+            equation_alpha: equation(gl::BLEND_EQUATION_ALPHA),
+            color,
+        }
+    }
+
+    /// Apply the state to the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn apply(&self) {
+        set_enabled(gl::BLEND, self.enabled);
+        crate::blend_func_separate(
+            self.src_rgb as GLenum,
+            self.dst_rgb as GLenum,
+            self.src_alpha as GLenum,
+            self.dst_alpha as GLenum,
+        );
+        crate::blend_equation_separate(self.equation_rgb as GLenum, self.equation_alpha as GLenum);
+        let [r, g, b, a] = self.color;
+        crate::blend_color(r, g, b, a);
+    }
+}
+
+impl Default for BlendState {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl_bindable_state!(BlendState, SAVED_BLEND_STATES);
+
+/// Depth test and depth write state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DepthState {
+    /// Enable the depth test.
+    pub test: bool,
+    /// Enable writing into the depth buffer.
+    pub write: bool,
+    pub func: CompareFunc,
+}
+
+impl DepthState {
+    /// Depth test and write disabled.
+    pub fn disabled() -> Self {
+        Self {
+            test: false,
+            write: false,
+            func: CompareFunc::Always,
+        }
+    }
+
+    /// Depth test with `func` and depth write enabled.
+    pub fn new(func: CompareFunc) -> Self {
+        Self {
+            test: true,
+            write: true,
+            func,
+        }
+    }
+
+    /// Depth test with `func` enabled, depth write disabled.
+    pub fn read_only(func: CompareFunc) -> Self {
+        Self {
+            write: false,
+            ..Self::new(func)
+        }
+    }
+
+    /// Query the current state of the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn current() -> Self {
+        let mut write: [GLboolean; 1] = [gl::TRUE];
+        crate::get_booleanv(gl::DEPTH_WRITEMASK, &mut write);
+        Self {
+            test: is_enabled(gl::DEPTH_TEST),
+            write: write[0] != gl::FALSE,
+            func: CompareFunc::from_raw(get_integer(gl::DEPTH_FUNC)).unwrap_or(CompareFunc::Less),
+        }
+    }
+
+    /// Apply the state to the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn apply(&self) {
+        set_enabled(gl::DEPTH_TEST, self.test);
+        crate::depth_mask(gl_bool(self.write));
+        crate::depth_func(self.func as GLenum);
+    }
+}
+
+impl Default for DepthState {
+    fn default() -> Self {
+        Self::new(CompareFunc::Less)
+    }
+}
+
+impl_bindable_state!(DepthState, SAVED_DEPTH_STATES);
+
+/// Stencil test and operations of one face.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StencilFace {
+    pub func: CompareFunc,
+    pub reference: GLint,
+    /// Mask ANDed with the reference and the stored value for the test.
+    pub read_mask: GLuint,
+    /// Mask of the bits written into the stencil buffer.
+    pub write_mask: GLuint,
+    /// Action when the stencil test fails.
+    pub fail: StencilOp,
+    /// Action when the stencil test passes but the depth test fails.
+    pub depth_fail: StencilOp,
+    /// Action when both the stencil and depth tests pass.
+    pub pass: StencilOp,
+}
+
+impl StencilFace {
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn current(face: GLenum) -> Self {
+        let (func, reference, read_mask, write_mask, fail, depth_fail, pass) = match face {
+            gl::BACK => (
+                gl::STENCIL_BACK_FUNC,
+                gl::STENCIL_BACK_REF,
+                gl::STENCIL_BACK_VALUE_MASK,
+                gl::STENCIL_BACK_WRITEMASK,
+                gl::STENCIL_BACK_FAIL,
+                gl::STENCIL_BACK_PASS_DEPTH_FAIL,
+                gl::STENCIL_BACK_PASS_DEPTH_PASS,
+            ),
+            _ => (
+                gl::STENCIL_FUNC,
+                gl::STENCIL_REF,
+                gl::STENCIL_VALUE_MASK,
+                gl::STENCIL_WRITEMASK,
+                gl::STENCIL_FAIL,
+                gl::STENCIL_PASS_DEPTH_FAIL,
+                gl::STENCIL_PASS_DEPTH_PASS,
+            ),
+        };
+        let op = |pname| StencilOp::from_raw(get_integer(pname)).unwrap_or(StencilOp::Keep);
+        Self {
+            func: CompareFunc::from_raw(get_integer(func)).unwrap_or(CompareFunc::Always),
+            reference: get_integer(reference),
+            read_mask: get_integer(read_mask) as GLuint,
+            write_mask: get_integer(write_mask) as GLuint,
+            fail: op(fail),
+            depth_fail: op(depth_fail),
+            pass: op(pass),
+        }
+    }
+
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn apply(&self, face: GLenum) {
+        crate::stencil_func_separate(face, self.func as GLenum, self.reference, self.read_mask);
+        crate::stencil_mask_separate(face, self.write_mask);
+        crate::stencil_op_separate(
+            face,
+            self.fail as GLenum,
+            self.depth_fail as GLenum,
+            self.pass as GLenum,
+        );
+    }
+}
+
+impl Default for StencilFace {
+    fn default() -> Self {
+        Self {
+            func: CompareFunc::Always,
+            reference: 0,
+            read_mask: !0,
+            write_mask: !0,
+            fail: StencilOp::Keep,
+            depth_fail: StencilOp::Keep,
+            pass: StencilOp::Keep,
+        }
+    }
+}
+
+/// Stencil test state of the front and back faces.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StencilState {
+    pub enabled: bool,
+    pub front: StencilFace,
+    pub back: StencilFace,
+}
+
+impl StencilState {
+    /// Stencil test enabled with same state for both faces.
+    pub fn new(face: StencilFace) -> Self {
+        Self {
+            enabled: true,
+            front: face,
+            back: face,
+        }
+    }
+
+    /// Stencil test enabled with separate states for the faces.
+    pub fn separate(front: StencilFace, back: StencilFace) -> Self {
+        Self {
+            enabled: true,
+            front,
+            back,
+        }
+    }
+
+    /// Query the current state of the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn current() -> Self {
+        Self {
+            enabled: is_enabled(gl::STENCIL_TEST),
+            front: StencilFace::current(gl::FRONT),
+            back: StencilFace::current(gl::BACK),
+        }
+    }
+
+    /// Apply the state to the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn apply(&self) {
+        set_enabled(gl::STENCIL_TEST, self.enabled);
+        self.front.apply(gl::FRONT);
+        self.back.apply(gl::BACK);
+    }
+}
+
+impl_bindable_state!(StencilState, SAVED_STENCIL_STATES);
+
+/// Depth offset of the polygons, see `glPolygonOffset`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolygonOffset {
+    pub factor: GLfloat,
+    pub units: GLfloat,
+}
+
+/// Rasterization state: face culling, winding and polygon offset.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RasterState {
+    /// Faces to cull, `None` disables the culling.
+    pub cull: Option<CullMode>,
+    pub front_face: FrontFace,
+    /// Polygon offset, `None` disables the offset.
+    pub polygon_offset: Option<PolygonOffset>,
+}
+
+impl RasterState {
+    /// Rasterization with `cull` faces culled.
+    pub fn new(cull: Option<CullMode>) -> Self {
+        Self {
+            cull,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_front_face(mut self, front_face: FrontFace) -> Self {
+        self.front_face = front_face;
+        self
+    }
+
+    pub fn with_polygon_offset(mut self, factor: GLfloat, units: GLfloat) -> Self {
+        self.polygon_offset = Some(PolygonOffset { factor, units });
+        self
+    }
+
+    /// Query the current state of the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn current() -> Self {
+        let cull = if is_enabled(gl::CULL_FACE) {
+            CullMode::from_raw(get_integer(gl::CULL_FACE_MODE))
+        } else {
+            None
+        };
+        let polygon_offset = if is_enabled(gl::POLYGON_OFFSET_FILL) {
+            Some(PolygonOffset {
+                factor: get_float(gl::POLYGON_OFFSET_FACTOR),
+                units: get_float(gl::POLYGON_OFFSET_UNITS),
+            })
+        } else {
+            None
+        };
+        Self {
+            cull,
+            front_face: FrontFace::from_raw(get_integer(gl::FRONT_FACE)).unwrap_or_default(),
+            polygon_offset,
+        }
+    }
+
+    /// Apply the state to the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn apply(&self) {
+        set_enabled(gl::CULL_FACE, self.cull.is_some());
+        if let Some(cull) = self.cull {
+            crate::cull_face(cull as GLenum);
+        }
+        crate::front_face(self.front_face as GLenum);
+        set_enabled(gl::POLYGON_OFFSET_FILL, self.polygon_offset.is_some());
+        if let Some(offset) = self.polygon_offset {
+            crate::polygon_offset(offset.factor, offset.units);
+        }
+    }
+}
+
+impl_bindable_state!(RasterState, SAVED_RASTER_STATES);
+
+/// Color channels written into the color buffers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorMask {
+    pub red: bool,
+    pub green: bool,
+    pub blue: bool,
+    pub alpha: bool,
+}
+
+impl ColorMask {
+    pub fn new(red: bool, green: bool, blue: bool, alpha: bool) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// All channels written.
+    pub fn all() -> Self {
+        Self::new(true, true, true, true)
+    }
+
+    /// No channel written.
+    pub fn none() -> Self {
+        Self::new(false, false, false, false)
+    }
+
+    /// Query the current state of the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn current() -> Self {
+        let mut mask: [GLboolean; 4] = [gl::TRUE; 4];
+        crate::get_booleanv(gl::COLOR_WRITEMASK, &mut mask);
+        let [r, g, b, a] = mask;
+        Self::new(
+            r != gl::FALSE,
+            g != gl::FALSE,
+            b != gl::FALSE,
+            a != gl::FALSE,
+        )
+    }
+
+    /// Apply the state to the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn apply(&self) {
+        crate::color_mask(
+            gl_bool(self.red),
+            gl_bool(self.green),
+            gl_bool(self.blue),
+            gl_bool(self.alpha),
+        );
+    }
+}
+
+impl Default for ColorMask {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl_bindable_state!(ColorMask, SAVED_COLOR_MASKS);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_raw() {
+        assert_eq!(
+            BlendFactor::from_raw(gl::ONE_MINUS_SRC_ALPHA as GLint),
+            Some(BlendFactor::OneMinusSrcAlpha)
+        );
+        assert_eq!(
+            StencilOp::from_raw(gl::INCR_WRAP as GLint),
+            Some(StencilOp::IncrWrap)
+        );
+        assert_eq!(
+            CompareFunc::from_raw(gl::LEQUAL as GLint),
+            Some(CompareFunc::LessEqual)
+        );
+        assert_eq!(CullMode::from_raw(gl::CCW as GLint), None);
+    }
+
+    #[test]
+    fn test_serde_blend_state() {
+        let blend = BlendState::alpha();
+        let json = serde_json::to_string(&blend).unwrap();
+        assert_eq!(serde_json::from_str::<BlendState>(&json).unwrap(), blend);
+        // The missing fields are filled from the default.
+        let partial: BlendState =
+            serde_json::from_str(r#"{"enabled": true, "dst_rgb": "OneMinusSrcAlpha"}"#).unwrap();
+        assert_eq!(
+            partial,
+            BlendState {
+                enabled: true,
+                dst_rgb: BlendFactor::OneMinusSrcAlpha,
+                ..BlendState::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_serde_stencil_state() {
+        let stencil = StencilState::separate(
+            StencilFace {
+                func: CompareFunc::Equal,
+                reference: 1,
+                pass: StencilOp::IncrWrap,
+                ..StencilFace::default()
+            },
+            StencilFace::default(),
+        );
+        let json = serde_json::to_string(&stencil).unwrap();
+        assert_eq!(
+            serde_json::from_str::<StencilState>(&json).unwrap(),
+            stencil
+        );
+        let partial: StencilState =
+            serde_json::from_str(r#"{"enabled": true, "front": {"reference": 3}}"#).unwrap();
+        assert!(partial.enabled);
+        assert_eq!(partial.front.reference, 3);
+        assert_eq!(partial.front.read_mask, !0);
+        assert_eq!(partial.back, StencilFace::default());
+        assert_eq!(
+            serde_json::from_str::<StencilState>("{}").unwrap(),
+            StencilState::default()
+        );
+    }
+}
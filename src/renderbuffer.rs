@@ -0,0 +1,139 @@
+use crate::gl::{GLenum, GLsizei, GLuint};
+use crate::prelude::*;
+#[cfg(all(
+    unix,
+    feature = "egls",
+    any(feature = "gles1", feature = "gles2", feature = "gles3")
+))]
+use crate::EglImage;
+use crate::StateCache;
+use crate::{gl, Finalizer, TextureFormat};
+use std::fmt;
+
+/// Renderbuffer object, the render target which cannot be sampled.
+pub struct Renderbuffer<'a> {
+    id: GLuint,
+    width: usize,
+    height: usize,
+    samples: usize,
+    finalizer: Option<Finalizer<'a, Renderbuffer<'a>>>,
+}
+
+impl<'a> Renderbuffer<'a> {
+    /// Create a `width` x `height` renderbuffer with sized `internal_format`.
+    /// # Note
+    /// The object is binded after created.
+    pub fn new(internal_format: TextureFormat, width: usize, height: usize) -> Self {
+        Self::with_samples(internal_format, width, height, 0)
+    }
+
+    /// Create a multisample renderbuffer, 0 `samples` disables multisampling.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn with_samples(
+        internal_format: TextureFormat,
+        width: usize,
+        height: usize,
+        samples: usize,
+    ) -> Self {
+        let renderbuffer = Self::generate(width, height, samples);
+        if samples > 0 {
+            crate::renderbuffer_storage_multisample(
+                gl::RENDERBUFFER,
+                samples as GLsizei,
+                internal_format as GLenum,
+                width as GLsizei,
+                height as GLsizei,
+            );
+        } else {
+            crate::renderbuffer_storage(
+                gl::RENDERBUFFER,
+                internal_format as GLenum,
+                width as GLsizei,
+                height as GLsizei,
+            );
+        }
+        renderbuffer
+    }
+
+    /// Create a renderbuffer which uses the `image` as its storage.
+    ///
+    /// Rendering into the renderbuffer updates the contents of the image, the
+    /// image is kept alive until the renderbuffer dropped.
+    #[cfg(all(
+        unix,
+        feature = "egls",
+        any(feature = "gles1", feature = "gles2", feature = "gles3")
+    ))]
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn from_egl_image(image: &EglImage<'a>) -> Self {
+        let mut renderbuffer = Self::generate(image.width(), image.height(), 0);
+        crate::egl_image_target_renderbuffer_storage_oes(gl::RENDERBUFFER, image.as_raw());
+        let image = image.clone();
+        renderbuffer.finalizer = Some(Box::new(move |_| {
+            let _ = &image;
+        }));
+        renderbuffer
+    }
+
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn generate(width: usize, height: usize, samples: usize) -> Self {
+        let renderbuffer = Self {
+            id: crate::new_renderbuffer(),
+            width,
+            height,
+            samples,
+            finalizer: None,
+        };
+        renderbuffer.bind();
+        renderbuffer
+    }
+
+    /// Returns the Id of the Renderbuffer.
+    pub fn id(&self) -> GLuint {
+        self.id
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+}
+
+impl<'a> fmt::Debug for Renderbuffer<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Renderbuffer")
+            .field("id", &self.id)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("samples", &self.samples)
+            .finish()
+    }
+}
+
+impl<'a> Drop for Renderbuffer<'a> {
+    fn drop(&mut self) {
+        if let Some(ref f) = self.finalizer.take() {
+            (f)(self);
+        }
+        StateCache::with(|cache| cache.forget_renderbuffer(self.id));
+        crate::delete_renderbuffers(&[self.id]);
+        self.id = 0;
+    }
+}
+
+impl<'a> Bindable for Renderbuffer<'a> {
+    fn bind(&self) {
+        StateCache::with(|cache| cache.bind_renderbuffer(self.id));
+    }
+
+    fn unbind(&self) {
+        StateCache::with(|cache| cache.unbind_renderbuffer(self.id));
+    }
+}
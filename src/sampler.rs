@@ -0,0 +1,170 @@
+use crate::{
+    gl,
+    gl::{GLfloat, GLint, GLuint},
+    prelude::*,
+    StateCache, TextureFilter, TextureWrap,
+};
+use serde::{Deserialize, Serialize};
+
+/// Comparison functions for depth comparison and depth/stencil tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareFunc {
+    Never = gl::NEVER as isize,
+    Less = gl::LESS as isize,
+    Equal = gl::EQUAL as isize,
+    LessEqual = gl::LEQUAL as isize,
+    Greater = gl::GREATER as isize,
+    NotEqual = gl::NOTEQUAL as isize,
+    GreaterEqual = gl::GEQUAL as isize,
+    Always = gl::ALWAYS as isize,
+}
+
+impl CompareFunc {
+    /// Returns the CompareFunc of the raw `GL_*` value.
+    pub fn from_raw(value: GLint) -> Option<Self> {
+        [
+            CompareFunc::Never,
+            CompareFunc::Less,
+            CompareFunc::Equal,
+            CompareFunc::LessEqual,
+            CompareFunc::Greater,
+            CompareFunc::NotEqual,
+            CompareFunc::GreaterEqual,
+            CompareFunc::Always,
+        ]
+        .iter()
+        .copied()
+        .find(|f| *f as GLint == value)
+    }
+}
+
+/// Sampler object which holds the sampling state separate from textures.
+///
+/// Bind to a texture unit with `bind_at(unit)` or `SlotBinder`, the sampler
+/// overrides the sampling parameters of any texture binded to the same unit.
+#[derive(Debug)]
+pub struct Sampler {
+    id: GLuint,
+}
+
+impl Sampler {
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn new() -> Self {
+        Self {
+            id: crate::new_sampler(),
+        }
+    }
+
+    /// Returns the Id of the Sampler.
+    pub fn id(&self) -> GLuint {
+        self.id
+    }
+
+    /// Set min filter parameter of the Sampler.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_min_filter(&self, filter: TextureFilter) {
+        crate::sampler_parameteri(self.id, gl::TEXTURE_MIN_FILTER, filter as GLint);
+    }
+
+    /// Set mag filter parameter of the Sampler.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_mag_filter(&self, filter: TextureFilter) {
+        crate::sampler_parameteri(self.id, gl::TEXTURE_MAG_FILTER, filter as GLint);
+    }
+
+    /// Set filter parameters of the Sampler.
+    pub fn set_filters(&self, min_filter: TextureFilter, mag_filter: TextureFilter) {
+        self.set_min_filter(min_filter);
+        self.set_mag_filter(mag_filter);
+    }
+
+    /// Set wrap S parameter of the Sampler.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_wrap_s(&self, wrap_s: TextureWrap) {
+        crate::sampler_parameteri(self.id, gl::TEXTURE_WRAP_S, wrap_s as GLint);
+    }
+
+    /// Set wrap T parameter of the Sampler.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_wrap_t(&self, wrap_t: TextureWrap) {
+        crate::sampler_parameteri(self.id, gl::TEXTURE_WRAP_T, wrap_t as GLint);
+    }
+
+    /// Set wrap R parameter of the Sampler.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_wrap_r(&self, wrap_r: TextureWrap) {
+        crate::sampler_parameteri(self.id, gl::TEXTURE_WRAP_R, wrap_r as GLint);
+    }
+
+    /// Set wrapping parameters of the Sampler.
+    pub fn set_wraps(&self, wrap_s: TextureWrap, wrap_t: TextureWrap, wrap_r: TextureWrap) {
+        self.set_wrap_s(wrap_s);
+        self.set_wrap_t(wrap_t);
+        self.set_wrap_r(wrap_r);
+    }
+
+    /// Set the minimum and maximum level-of-detail of the Sampler.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_lod_range(&self, min_lod: GLfloat, max_lod: GLfloat) {
+        crate::sampler_parameterf(self.id, gl::TEXTURE_MIN_LOD, min_lod);
+        crate::sampler_parameterf(self.id, gl::TEXTURE_MAX_LOD, max_lod);
+    }
+
+    /// Set the depth comparison function, `None` disables the comparison.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_compare(&self, func: Option<CompareFunc>) {
+        match func {
+            Some(func) => {
+                crate::sampler_parameteri(
+                    self.id,
+                    gl::TEXTURE_COMPARE_MODE,
+                    gl::COMPARE_REF_TO_TEXTURE as GLint,
+                );
+                crate::sampler_parameteri(self.id, gl::TEXTURE_COMPARE_FUNC, func as GLint);
+            }
+            None => {
+                crate::sampler_parameteri(self.id, gl::TEXTURE_COMPARE_MODE, gl::NONE as GLint);
+            }
+        }
+    }
+
+    /// Set the maximum degree of anisotropy of the Sampler.
+    ///
+    /// # Note
+    /// Requires `GL_EXT_texture_filter_anisotropic`, 1.0 disables the anisotropic filtering.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_max_anisotropy(&self, max_anisotropy: GLfloat) {
+        crate::sampler_parameterf(self.id, gl::TEXTURE_MAX_ANISOTROPY_EXT, max_anisotropy);
+    }
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        StateCache::with(|cache| cache.forget_sampler(self.id));
+        crate::delete_samplers(&[self.id]);
+    }
+}
+
+impl Bindable for Sampler {
+    fn bind(&self) {
+        self.bind_at(0);
+    }
+
+    fn bind_at(&self, unit: u32) {
+        StateCache::with(|cache| cache.bind_sampler(unit, self.id));
+    }
+
+    fn unbind(&self) {
+        self.unbind_at(0);
+    }
+
+    fn unbind_at(&self, unit: u32) {
+        StateCache::with(|cache| cache.unbind_sampler(unit, self.id));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{gl, prelude::Bindable, GLint, StateCache, Viewport};
+use std::cell::RefCell;
+
+thread_local! {
+    static CLIP_STACK: RefCell<ClipStack> = const {
+        RefCell::new(ClipStack {
+            clips: Vec::new(),
+            previous: None,
+        })
+    };
+}
+
+/// Stack of the binded scissors with the scissor state before the outermost one.
+#[derive(Debug)]
+struct ClipStack {
+    clips: Vec<Scissor>,
+    previous: Option<(bool, Scissor)>,
+}
+
+impl ClipStack {
+    /// Push the `scissor` and returns the clip to apply, `previous` is called
+    /// for the outermost scissor to save the state of the context.
+    fn push<F>(&mut self, scissor: &Scissor, previous: F) -> Scissor
+    where
+        F: FnOnce() -> (bool, Scissor),
+    {
+        let clip = match self.clips.last() {
+            Some(outer) => outer.intersect(scissor),
+            None => {
+                self.previous = Some(previous());
+                *scissor
+            }
+        };
+        self.clips.push(clip);
+        clip
+    }
+
+    /// Pop the innermost scissor and returns the state to restore.
+    fn pop(&mut self) -> Option<(bool, Scissor)> {
+        self.clips.pop()?;
+        match self.clips.last() {
+            Some(outer) => Some((true, *outer)),
+            None => self.previous.take(),
+        }
+    }
+}
+
+/// Scissor rectangle, binding enables `GL_SCISSOR_TEST` with the rectangle.
+///
+/// Nested scissors clip to the intersection with the outer ones, and the
+/// outer rectangle is restored on unbind. The scissor test and rectangle in
+/// effect before the outermost scissor are restored when it unbinded.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Scissor {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl Scissor {
+    pub fn with_offset(x: i32, y: i32, w: i32, h: i32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    pub fn with_size(w: i32, h: i32) -> Self {
+        Self { x: 0, y: 0, w, h }
+    }
+
+    /// Returns the overlapping area of the rectangles, empty if they do not overlap.
+    pub fn intersect(&self, other: &Scissor) -> Scissor {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.w).min(other.x + other.w);
+        let top = (self.y + self.h).min(other.y + other.h);
+        Scissor {
+            x,
+            y,
+            w: (right - x).max(0),
+            h: (top - y).max(0),
+        }
+    }
+
+    /// Returns `true` if the rectangle has no area.
+    pub fn is_empty(&self) -> bool {
+        self.w <= 0 || self.h <= 0
+    }
+
+    /// Returns the effective clip rectangle of the binded scissors.
+    pub fn current_clip() -> Option<Scissor> {
+        CLIP_STACK.with(|stack| stack.borrow().clips.last().copied())
+    }
+
+    /// Query the current scissor rectangle of the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn current() -> Self {
+        let mut rect: [GLint; 4] = [0; 4];
+        crate::get_integerv(gl::SCISSOR_BOX, &mut rect);
+        Self::with_offset(rect[0], rect[1], rect[2], rect[3])
+    }
+
+    fn apply(&self, enabled: bool) {
+        StateCache::with(|cache| cache.set_enabled(gl::SCISSOR_TEST, enabled));
+        crate::scissor(self.x, self.y, self.w, self.h);
+    }
+}
+
+impl From<Viewport> for Scissor {
+    fn from(viewport: Viewport) -> Self {
+        Self::with_offset(viewport.x, viewport.y, viewport.w, viewport.h)
+    }
+}
+
+impl Bindable for Scissor {
+    fn bind(&self) {
+        let clip = CLIP_STACK.with(|stack| {
+            stack.borrow_mut().push(self, || {
+                let enabled = StateCache::with(|cache| cache.is_enabled(gl::SCISSOR_TEST));
+                (enabled, Scissor::current())
+            })
+        });
+        clip.apply(true);
+    }
+
+    fn unbind(&self) {
+        if let Some((enabled, clip)) = CLIP_STACK.with(|stack| stack.borrow_mut().pop()) {
+            clip.apply(enabled);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersect() {
+        let outer = Scissor::with_offset(10, 10, 100, 50);
+        let inner = Scissor::with_offset(60, 0, 100, 30);
+        assert_eq!(
+            outer.intersect(&inner),
+            Scissor::with_offset(60, 10, 50, 20)
+        );
+        assert_eq!(inner.intersect(&outer), outer.intersect(&inner));
+        let apart = Scissor::with_offset(200, 200, 10, 10);
+        assert!(outer.intersect(&apart).is_empty());
+    }
+
+    #[test]
+    fn test_nested_clips() {
+        let mut stack = ClipStack {
+            clips: Vec::new(),
+            previous: None,
+        };
+        let previous = (true, Scissor::with_size(640, 480));
+        let outer = Scissor::with_offset(10, 10, 100, 50);
+        let inner = Scissor::with_offset(60, 0, 100, 30);
+        assert_eq!(stack.push(&outer, || previous), outer);
+        assert_eq!(
+            stack.push(&inner, || unreachable!()),
+            Scissor::with_offset(60, 10, 50, 20)
+        );
+        assert_eq!(stack.pop(), Some((true, outer)));
+        // The scissor of the caller is restored with the outermost one.
+        assert_eq!(stack.pop(), Some(previous));
+        assert_eq!(stack.pop(), None);
+
+        let disabled = (false, Scissor::with_size(1, 1));
+        stack.push(&outer, || disabled);
+        assert_eq!(stack.pop(), Some(disabled));
+    }
+}
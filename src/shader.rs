@@ -1,6 +1,6 @@
 use crate::{
-    gl, prelude::*, Error, GLenum, GLfloat, GLint, GLuint, Matrix4, Variant, Vector2, Vector3,
-    Vector4,
+    gl, prelude::*, Error, FeedbackMode, GLenum, GLfloat, GLint, GLuint, Matrix4, StateCache,
+    Variant, Vector2, Vector3, Vector4,
 };
 use std::fs::File;
 use std::io::Read;
@@ -138,10 +138,54 @@ impl Program {
 
     /// Build a program from a list of the pre-compiled shaders.
     pub fn from_shaders(shaders: &[Shader]) -> Result<Program, String> {
+        Program::link(shaders, |_| {})
+    }
+
+    /// Build a program from a list of the pre-compiled shaders, capturing the
+    /// `varyings` outputs with transform feedback.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn from_shaders_with_varyings<T>(
+        shaders: &[Shader],
+        varyings: &[T],
+        mode: FeedbackMode,
+    ) -> Result<Program, String>
+    where
+        T: AsRef<str>,
+    {
+        Program::link(shaders, |program_id| {
+            crate::transform_feedback_varyings(program_id, varyings, mode as GLenum)
+        })
+    }
+
+    /// Build a program from a list of the sources, capturing the `varyings`
+    /// outputs with transform feedback.
+    pub fn from_sources_with_varyings<T, V>(
+        sources: &[(T, GLenum)],
+        varyings: &[V],
+        mode: FeedbackMode,
+    ) -> Result<Program, String>
+    where
+        T: AsRef<str> + std::fmt::Debug,
+        V: AsRef<str>,
+    {
+        let shaders: Vec<_> = sources
+            .iter()
+            .map(|(s, k)| Shader::from_source(s, *k).unwrap())
+            .collect();
+        Program::from_shaders_with_varyings(&shaders[0..], varyings, mode)
+    }
+
+    /// Link the shaders, `prelink` configures the program before linking.
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn link<F>(shaders: &[Shader], prelink: F) -> Result<Program, String>
+    where
+        F: FnOnce(GLuint),
+    {
         let program_id = crate::create_program().unwrap();
         for shader in shaders {
             crate::attach_shader(program_id, shader.id());
         }
+        prelink(program_id);
         crate::link_program(program_id);
         match crate::get_programiv(program_id, gl::LINK_STATUS) {
             0 => Err(crate::get_program_info_log(program_id).unwrap()),
@@ -149,7 +193,7 @@ impl Program {
                 for shader in shaders {
                     crate::detach_shader(program_id, shader.id());
                 }
-                crate::use_program(program_id);
+                StateCache::with(|cache| cache.use_program(program_id));
                 Ok(Program { id: program_id })
             }
         }
@@ -172,7 +216,20 @@ impl Program {
         self.id
     }
 
+    /// Set the label of the program shown in the debug messages and the debuggers.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_label(&self, label: &str) {
+        crate::object_label(gl::PROGRAM, self.id(), label);
+    }
+
+    /// Returns the label of the program, empty if not labeled.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn label(&self) -> String {
+        crate::get_object_label(gl::PROGRAM, self.id())
+    }
+
     /// Bind the attribute with `name` to specified `location`.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn bind_attrib<T>(&self, name: T, location: GLuint)
     where
         T: AsRef<str>,
@@ -181,6 +238,7 @@ impl Program {
     }
 
     /// Returns the location of the attribute with `name`.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn locate_attrib<T>(&self, name: T) -> Result<GLint, Error>
     where
         T: AsRef<str>,
@@ -189,6 +247,7 @@ impl Program {
     }
 
     /// Returns the location of the uniform with `name`.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn locate_uniform<T>(&self, name: T) -> Result<GLint, Error>
     where
         T: AsRef<str>,
@@ -197,6 +256,7 @@ impl Program {
     }
 
     /// Update the `value` of the uniform with specified `location`.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_uniform(&self, location: GLint, value: UniformValue) {
         match value {
             UniformValue::Int(v) => crate::uniform1i(location, v),
@@ -212,17 +272,18 @@ impl Program {
 
 impl Drop for Program {
     fn drop(&mut self) {
+        StateCache::with(|cache| cache.forget_program(self.id));
         crate::delete_program(self.id);
     }
 }
 
 impl Bindable for Program {
     fn bind(&self) {
-        crate::use_program(self.id);
+        StateCache::with(|cache| cache.use_program(self.id));
     }
 
     fn unbind(&self) {
-        crate::use_program(0);
+        StateCache::with(|cache| cache.unbind_program(self.id));
     }
 }
 
@@ -270,6 +331,7 @@ impl Shader {
         Shader::from_source(source, kind)
     }
 
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn from_source<T>(source: T, kind: GLenum) -> Result<Shader, String>
     where
         T: AsRef<str>,
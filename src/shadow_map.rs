@@ -0,0 +1,157 @@
+use crate::{
+    gl, prelude::*, FramebufferObject, GLfloat, Matrix4, Point3, Texture, TextureFormat, Vector3,
+    Viewport,
+};
+
+/// Depth-only render target for shadow mapping.
+///
+/// Binding the object renders into the shadow map, the depth texture is
+/// sampled afterwards with `sampler2DShadow` and `shadow_matrix()`.
+#[derive(Debug)]
+pub struct ShadowMap<'a> {
+    fbo: FramebufferObject<'a>,
+    viewport: Viewport,
+    light_space: Matrix4,
+}
+
+impl<'a> ShadowMap<'a> {
+    /// Create a square shadow map of `size` pixels with 24 bits depth.
+    pub fn new(size: usize) -> Result<Self, String> {
+        Self::with_format(size, size, TextureFormat::DepthComponent24)
+    }
+
+    /// Create a shadow map of `width` x `height` pixels with a depth `format`.
+    pub fn with_format(width: usize, height: usize, format: TextureFormat) -> Result<Self, String> {
+        let fbo = FramebufferObject::with_depth_texture(width, height, format)?;
+        fbo.unbind();
+        Ok(Self {
+            fbo,
+            viewport: Viewport::with_size(width as i32, height as i32),
+            light_space: Matrix4::identity(),
+        })
+    }
+
+    /// Set up a directional light shining along `direction`, covering the
+    /// sphere at `center` with `radius`.
+    pub fn set_directional_light(&mut self, direction: &Vector3, center: &Point3, radius: GLfloat) {
+        self.light_space = directional_light_space(direction, center, radius);
+    }
+
+    /// Set up a spot light at `position` looking at `target` with vertical
+    /// field of view `fovy` in radians.
+    pub fn set_spot_light(
+        &mut self,
+        position: &Point3,
+        target: &Point3,
+        fovy: GLfloat,
+        znear: GLfloat,
+        zfar: GLfloat,
+    ) {
+        let direction = (target - position).normalize();
+        let view = Matrix4::look_at_rh(position, target, &Self::up_vector(&direction));
+        let aspect = self.viewport.w as GLfloat / self.viewport.h as GLfloat;
+        let proj = Matrix4::new_perspective(aspect, fovy, znear, zfar);
+        self.light_space = proj * view;
+    }
+
+    /// Returns the matrix which transforms world space to light clip space.
+    pub fn light_space_matrix(&self) -> Matrix4 {
+        self.light_space
+    }
+
+    /// Returns the matrix which transforms world space to shadow map texture
+    /// coordinates and depth in `[0, 1]`.
+    pub fn shadow_matrix(&self) -> Matrix4 {
+        shadow_bias() * self.light_space
+    }
+
+    /// Returns the depth texture of the shadow map.
+    pub fn texture(&self) -> &Texture<'a> {
+        self.fbo.texture()
+    }
+
+    /// Returns the depth framebuffer object of the shadow map.
+    pub fn framebuffer_object(&self) -> &FramebufferObject<'a> {
+        &self.fbo
+    }
+
+    fn up_vector(direction: &Vector3) -> Vector3 {
+        if direction.y.abs() > 0.99 {
+            Vector3::z()
+        } else {
+            Vector3::y()
+        }
+    }
+}
+
+/// Returns the light space matrix of a directional light shining along
+/// `direction`, covering the sphere at `center` with `radius`.
+fn directional_light_space(direction: &Vector3, center: &Point3, radius: GLfloat) -> Matrix4 {
+    let direction = direction.normalize();
+    let eye = center - direction * radius;
+    let view = Matrix4::look_at_rh(&eye, center, &ShadowMap::up_vector(&direction));
+    let proj = Matrix4::new_orthographic(-radius, radius, -radius, radius, 0.0, radius * 2.0);
+    proj * view
+}
+
+/// Returns the matrix which maps clip space `[-1, 1]` to `[0, 1]`.
+fn shadow_bias() -> Matrix4 {
+    Matrix4::new_translation(&Vector3::new(0.5, 0.5, 0.5)) * Matrix4::new_scaling(0.5)
+}
+
+impl<'a> Bindable for ShadowMap<'a> {
+    fn bind(&self) {
+        self.fbo.framebuffer().bind();
+        self.viewport.bind();
+        // Unlike glClear, the clear depth value of the context is untouched.
+        crate::clear_bufferfv(gl::DEPTH, 0, &[1.0]);
+    }
+
+    fn unbind(&self) {
+        self.viewport.unbind();
+        self.fbo.framebuffer().unbind();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(matrix: &Matrix4, point: &Point3) -> Point3 {
+        matrix.transform_point(point)
+    }
+
+    #[test]
+    fn test_directional_shadow_matrix() {
+        let center = Point3::new(1.0, 2.0, 3.0);
+        let direction = Vector3::new(0.0, 0.0, -2.0);
+        let shadow = shadow_bias() * directional_light_space(&direction, &center, 4.0);
+        // The center of the sphere is in the middle of the shadow map.
+        let p = transform(&shadow, &center);
+        assert!((p - Point3::new(0.5, 0.5, 0.5)).norm() < 1e-5);
+        // The depth grows along the light direction from 0 to 1.
+        let near = transform(&shadow, &(center + Vector3::z() * 4.0));
+        let far = transform(&shadow, &(center - Vector3::z() * 4.0));
+        assert!(near.z.abs() < 1e-5);
+        assert!((far.z - 1.0).abs() < 1e-5);
+        // The edges of the sphere are at the edges of the shadow map.
+        let edge = transform(&shadow, &(center + Vector3::x() * 4.0));
+        assert!((edge.x - 1.0).abs() < 1e-5);
+        assert!((edge.y - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_up_vector() {
+        assert_eq!(
+            ShadowMap::up_vector(&Vector3::new(0.0, -1.0, 0.0)),
+            Vector3::z()
+        );
+        assert_eq!(
+            ShadowMap::up_vector(&Vector3::new(1.0, -1.0, 0.0).normalize()),
+            Vector3::y()
+        );
+        // A light straight down still gives a valid matrix.
+        let light = directional_light_space(&-Vector3::y(), &Point3::origin(), 1.0);
+        assert!(light.iter().all(|v| v.is_finite()));
+    }
+}
@@ -0,0 +1,610 @@
+use crate::{gl, GLenum, GLint, GLsizei, GLuint};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    static STATE_CACHE: RefCell<StateCache> = RefCell::new(StateCache::new());
+}
+
+type ViewportRect = (GLint, GLint, GLsizei, GLsizei);
+
+/// Binding points tracked by the StateCache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Binding {
+    Program,
+    VertexArray,
+    Buffer(GLenum),
+    ActiveTexture,
+    /// Texture unit and target.
+    Texture(GLuint, GLenum),
+    /// `GL_DRAW_FRAMEBUFFER` or `GL_READ_FRAMEBUFFER`.
+    Framebuffer(GLenum),
+    Renderbuffer,
+    /// Texture unit.
+    Sampler(GLuint),
+    TransformFeedback,
+}
+
+impl Binding {
+    /// Bind `object` to the binding point.
+    fn apply(self, object: GLuint) {
+        #[cfg(test)]
+        tests::record(self, object);
+        #[cfg(not(test))]
+        match self {
+            Binding::Program => crate::use_program(object),
+            Binding::VertexArray => crate::bind_vertex_array(object),
+            Binding::Buffer(target) => crate::bind_buffer(target, object),
+            Binding::ActiveTexture => crate::active_texture(gl::TEXTURE0 + object),
+            // The texture unit is activated by the caller.
+            Binding::Texture(_, target) => crate::bind_texture(target, object),
+            Binding::Framebuffer(target) => crate::bind_framebuffer(target, object),
+            Binding::Renderbuffer => crate::bind_renderbuffer(gl::RENDERBUFFER, object),
+            Binding::Sampler(unit) => crate::bind_sampler(unit, object),
+            Binding::TransformFeedback => {
+                crate::bind_transform_feedback(gl::TRANSFORM_FEEDBACK, object)
+            }
+        }
+    }
+
+    /// Query the object bound to the binding point from the context.
+    fn query(self) -> GLuint {
+        let pname = match self {
+            Binding::Program => gl::CURRENT_PROGRAM,
+            Binding::VertexArray => gl::VERTEX_ARRAY_BINDING,
+            Binding::Buffer(target) => match target {
+                gl::ARRAY_BUFFER => gl::ARRAY_BUFFER_BINDING,
+                gl::ELEMENT_ARRAY_BUFFER => gl::ELEMENT_ARRAY_BUFFER_BINDING,
+                gl::PIXEL_PACK_BUFFER => gl::PIXEL_PACK_BUFFER_BINDING,
+                gl::PIXEL_UNPACK_BUFFER => gl::PIXEL_UNPACK_BUFFER_BINDING,
+                gl::UNIFORM_BUFFER => gl::UNIFORM_BUFFER_BINDING,
+                gl::COPY_READ_BUFFER => gl::COPY_READ_BUFFER_BINDING,
+                gl::COPY_WRITE_BUFFER => gl::COPY_WRITE_BUFFER_BINDING,
+                gl::TRANSFORM_FEEDBACK_BUFFER => gl::TRANSFORM_FEEDBACK_BUFFER_BINDING,
+                gl::DRAW_INDIRECT_BUFFER => gl::DRAW_INDIRECT_BUFFER_BINDING,
+                _ => return 0,
+            },
+            Binding::ActiveTexture => gl::ACTIVE_TEXTURE,
+            Binding::Texture(_, target) => match target {
+                gl::TEXTURE_2D => gl::TEXTURE_BINDING_2D,
+                gl::TEXTURE_2D_ARRAY => gl::TEXTURE_BINDING_2D_ARRAY,
+                gl::TEXTURE_3D => gl::TEXTURE_BINDING_3D,
+                gl::TEXTURE_CUBE_MAP => gl::TEXTURE_BINDING_CUBE_MAP,
+                #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+                gl::TEXTURE_EXTERNAL_OES => gl::TEXTURE_BINDING_EXTERNAL_OES,
+                _ => return 0,
+            },
+            Binding::Framebuffer(gl::READ_FRAMEBUFFER) => gl::READ_FRAMEBUFFER_BINDING,
+            Binding::Framebuffer(_) => gl::DRAW_FRAMEBUFFER_BINDING,
+            Binding::Renderbuffer => gl::RENDERBUFFER_BINDING,
+            // The texture unit is activated by the caller.
+            Binding::Sampler(_) => gl::SAMPLER_BINDING,
+            Binding::TransformFeedback => gl::TRANSFORM_FEEDBACK_BINDING,
+        };
+        let mut value: [GLint; 1] = [0];
+        crate::get_integerv(pname, &mut value);
+        match self {
+            Binding::ActiveTexture => value[0] as GLuint - gl::TEXTURE0,
+            _ => value[0] as GLuint,
+        }
+    }
+}
+
+/// Bindings saved by a scope, in the order they were first changed.
+#[derive(Debug, Default)]
+struct Scope {
+    bindings: Vec<(Binding, GLuint)>,
+    viewport: Option<ViewportRect>,
+}
+
+/// Cache of the context state which skips the redundant GL calls.
+///
+/// The objects of this crate bind through the cache of the current thread,
+/// which tracks the state of the context current on the thread. Call
+/// `StateCache::invalidate()` after changing the state with raw `gl::` or
+/// `crate::` calls, or after making another context current.
+///
+/// The cache also saves the bindings replaced inside a scope, see
+/// `begin_scope()`, so `AutoBinder` restores the bindings of the outer scope
+/// instead of binding `0`.
+#[derive(Debug, Default)]
+pub struct StateCache {
+    bindings: HashMap<Binding, GLuint>,
+    viewport: Option<ViewportRect>,
+    enables: HashMap<GLenum, bool>,
+    scopes: Vec<Scope>,
+}
+
+impl StateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` with the cache of the current thread.
+    pub fn with<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut StateCache) -> R,
+    {
+        STATE_CACHE.with(|cache| f(&mut cache.borrow_mut()))
+    }
+
+    /// Forget all cached state of the current thread, the next calls are
+    /// issued unconditionally. The bindings saved by the scopes are kept.
+    pub fn invalidate() {
+        Self::with(|cache| {
+            cache.bindings.clear();
+            cache.viewport = None;
+            cache.enables.clear();
+        });
+    }
+
+    /// Begin a scope, the bindings changed until `end_scope()` are restored
+    /// when the scope ends. Returns the depth of the scope, counted from 1.
+    pub fn begin_scope(&mut self) -> usize {
+        self.scopes.push(Scope::default());
+        self.scopes.len()
+    }
+
+    /// End the scope at `depth` and the scopes nested in it, and restore the
+    /// bindings they saved. Does nothing if the scope has ended.
+    pub fn end_scope(&mut self, depth: usize) {
+        while depth > 0 && self.scopes.len() >= depth {
+            let scope = self.scopes.pop().unwrap();
+            for (binding, object) in scope.bindings.into_iter().rev() {
+                self.restore(binding, object);
+            }
+            if let Some((x, y, width, height)) = scope.viewport {
+                self.set_viewport(x, y, width, height);
+            }
+        }
+    }
+
+    /// Install `program` for rendering.
+    pub fn use_program(&mut self, program: GLuint) {
+        self.set(Binding::Program, program);
+    }
+
+    /// Release `program`, restores the program of the outer scope.
+    pub fn unbind_program(&mut self, program: GLuint) {
+        self.release(Binding::Program, program);
+    }
+
+    /// Bind the vertex array `array`.
+    pub fn bind_vertex_array(&mut self, array: GLuint) {
+        self.set(Binding::VertexArray, array);
+    }
+
+    /// Release the vertex array `array`, restores the vertex array of the
+    /// outer scope.
+    pub fn unbind_vertex_array(&mut self, array: GLuint) {
+        self.release(Binding::VertexArray, array);
+    }
+
+    /// Bind `buffer` to `target`.
+    pub fn bind_buffer(&mut self, target: GLenum, buffer: GLuint) {
+        self.set(Binding::Buffer(target), buffer);
+    }
+
+    /// Release `buffer` from `target`, restores the buffer of the outer scope.
+    pub fn unbind_buffer(&mut self, target: GLenum, buffer: GLuint) {
+        self.release(Binding::Buffer(target), buffer);
+    }
+
+    /// Returns the buffer cached for `target`.
+    pub fn buffer(&self, target: GLenum) -> Option<GLuint> {
+        self.bindings.get(&Binding::Buffer(target)).copied()
+    }
+
+    /// Select the texture unit `unit`, counted from `GL_TEXTURE0`.
+    pub fn active_texture(&mut self, unit: GLuint) {
+        self.set(Binding::ActiveTexture, unit);
+    }
+
+    /// Bind `texture` to `target` of the active texture unit.
+    pub fn bind_texture(&mut self, target: GLenum, texture: GLuint) {
+        let unit = self.current(Binding::ActiveTexture);
+        self.bind_texture_at(unit, target, texture);
+    }
+
+    /// Release `texture` from `target` of the active texture unit.
+    pub fn unbind_texture(&mut self, target: GLenum, texture: GLuint) {
+        let unit = self.current(Binding::ActiveTexture);
+        self.unbind_texture_at(unit, target, texture);
+    }
+
+    /// Bind `texture` to `target` of the texture unit `unit`.
+    pub fn bind_texture_at(&mut self, unit: GLuint, target: GLenum, texture: GLuint) {
+        self.set(Binding::Texture(unit, target), texture);
+    }
+
+    /// Release `texture` from `target` of the texture unit `unit`, restores the
+    /// texture of the outer scope.
+    pub fn unbind_texture_at(&mut self, unit: GLuint, target: GLenum, texture: GLuint) {
+        self.release(Binding::Texture(unit, target), texture);
+    }
+
+    /// Bind `framebuffer` to `target`, `GL_FRAMEBUFFER` binds both the draw
+    /// and read targets.
+    pub fn bind_framebuffer(&mut self, target: GLenum, framebuffer: GLuint) {
+        match target {
+            gl::FRAMEBUFFER => {
+                let draw = Binding::Framebuffer(gl::DRAW_FRAMEBUFFER);
+                let read = Binding::Framebuffer(gl::READ_FRAMEBUFFER);
+                if self.bindings.get(&draw) != Some(&framebuffer)
+                    || self.bindings.get(&read) != Some(&framebuffer)
+                {
+                    self.save(draw);
+                    self.save(read);
+                    Binding::Framebuffer(gl::FRAMEBUFFER).apply(framebuffer);
+                    self.bindings.insert(draw, framebuffer);
+                    self.bindings.insert(read, framebuffer);
+                }
+            }
+            _ => self.set(Binding::Framebuffer(target), framebuffer),
+        }
+    }
+
+    /// Release `framebuffer` from `target`, restores the framebuffer of the
+    /// outer scope.
+    pub fn unbind_framebuffer(&mut self, target: GLenum, framebuffer: GLuint) {
+        match target {
+            gl::FRAMEBUFFER => {
+                self.release(Binding::Framebuffer(gl::DRAW_FRAMEBUFFER), framebuffer);
+                self.release(Binding::Framebuffer(gl::READ_FRAMEBUFFER), framebuffer);
+            }
+            _ => self.release(Binding::Framebuffer(target), framebuffer),
+        }
+    }
+
+    /// Bind `renderbuffer` to `GL_RENDERBUFFER`.
+    pub fn bind_renderbuffer(&mut self, renderbuffer: GLuint) {
+        self.set(Binding::Renderbuffer, renderbuffer);
+    }
+
+    /// Release `renderbuffer`, restores the renderbuffer of the outer scope.
+    pub fn unbind_renderbuffer(&mut self, renderbuffer: GLuint) {
+        self.release(Binding::Renderbuffer, renderbuffer);
+    }
+
+    /// Bind `sampler` to the texture unit `unit`.
+    pub fn bind_sampler(&mut self, unit: GLuint, sampler: GLuint) {
+        self.set(Binding::Sampler(unit), sampler);
+    }
+
+    /// Release `sampler` from the texture unit `unit`, restores the sampler of
+    /// the outer scope.
+    pub fn unbind_sampler(&mut self, unit: GLuint, sampler: GLuint) {
+        self.release(Binding::Sampler(unit), sampler);
+    }
+
+    /// Bind the transform feedback object `feedback`.
+    pub fn bind_transform_feedback(&mut self, feedback: GLuint) {
+        self.set(Binding::TransformFeedback, feedback);
+    }
+
+    /// Release the transform feedback object `feedback`, restores the object
+    /// of the outer scope.
+    pub fn unbind_transform_feedback(&mut self, feedback: GLuint) {
+        self.release(Binding::TransformFeedback, feedback);
+    }
+
+    /// Set the viewport.
+    pub fn viewport(&mut self, x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
+        if self.viewport != Some((x, y, width, height)) {
+            if let Some(scope) = self.scopes.last() {
+                if scope.viewport.is_none() {
+                    let current = self.current_viewport();
+                    self.scopes.last_mut().unwrap().viewport = Some(current);
+                }
+            }
+            self.set_viewport(x, y, width, height);
+        }
+    }
+
+    /// Restore the viewport saved by the innermost scope which changed it.
+    pub fn restore_viewport(&mut self) {
+        let saved = self.scopes.iter().rev().find_map(|scope| scope.viewport);
+        if let Some((x, y, width, height)) = saved {
+            self.set_viewport(x, y, width, height);
+        }
+    }
+
+    /// Enable or disable the capability `cap`.
+    pub fn set_enabled(&mut self, cap: GLenum, enabled: bool) {
+        if self.enables.get(&cap) != Some(&enabled) {
+            if enabled {
+                crate::enable(cap);
+            } else {
+                crate::disable(cap);
+            }
+            self.enables.insert(cap, enabled);
+        }
+    }
+
+    /// Returns `true` if the capability `cap` is enabled, queries the context
+    /// only if not cached.
+    pub fn is_enabled(&mut self, cap: GLenum) -> bool {
+        *self
+            .enables
+            .entry(cap)
+            .or_insert_with(|| crate::is_enabled(cap))
+    }
+
+    /// Forget the buffer bound to `target`.
+    pub fn invalidate_buffer(&mut self, target: GLenum) {
+        self.bindings.remove(&Binding::Buffer(target));
+    }
+
+    /// Forget the renderbuffer bound to `GL_RENDERBUFFER`.
+    pub fn invalidate_renderbuffer(&mut self) {
+        self.bindings.remove(&Binding::Renderbuffer);
+    }
+
+    /// Forget the sampler bound to the texture unit `unit`.
+    pub fn invalidate_sampler(&mut self, unit: GLuint) {
+        self.bindings.remove(&Binding::Sampler(unit));
+    }
+
+    /// Forget the bindings of the deleted `program`.
+    pub(crate) fn forget_program(&mut self, program: GLuint) {
+        self.forget(program, |b| b == Binding::Program);
+    }
+
+    /// Forget the bindings of the deleted vertex array `array`.
+    pub(crate) fn forget_vertex_array(&mut self, array: GLuint) {
+        if self.bindings.get(&Binding::VertexArray) == Some(&array) {
+            self.bindings
+                .remove(&Binding::Buffer(gl::ELEMENT_ARRAY_BUFFER));
+        }
+        self.forget(array, |b| b == Binding::VertexArray);
+    }
+
+    /// Forget the bindings of the deleted `buffer`.
+    pub(crate) fn forget_buffer(&mut self, buffer: GLuint) {
+        self.forget(buffer, |b| matches!(b, Binding::Buffer(_)));
+    }
+
+    /// Forget the bindings of the deleted `texture`.
+    pub(crate) fn forget_texture(&mut self, texture: GLuint) {
+        self.forget(texture, |b| matches!(b, Binding::Texture(..)));
+    }
+
+    /// Forget the bindings of the deleted `framebuffer`.
+    pub(crate) fn forget_framebuffer(&mut self, framebuffer: GLuint) {
+        self.forget(framebuffer, |b| matches!(b, Binding::Framebuffer(_)));
+    }
+
+    /// Forget the bindings of the deleted `renderbuffer`.
+    pub(crate) fn forget_renderbuffer(&mut self, renderbuffer: GLuint) {
+        self.forget(renderbuffer, |b| b == Binding::Renderbuffer);
+    }
+
+    /// Forget the bindings of the deleted transform feedback object `feedback`.
+    pub(crate) fn forget_transform_feedback(&mut self, feedback: GLuint) {
+        self.forget(feedback, |b| b == Binding::TransformFeedback);
+    }
+
+    /// Forget the bindings of the deleted `sampler`.
+    pub(crate) fn forget_sampler(&mut self, sampler: GLuint) {
+        self.forget(sampler, |b| matches!(b, Binding::Sampler(_)));
+    }
+
+    /// Returns the object bound to `binding`, queries the context if not cached.
+    fn current(&mut self, binding: Binding) -> GLuint {
+        if let Some(object) = self.bindings.get(&binding) {
+            return *object;
+        }
+        if let Binding::Texture(unit, _) | Binding::Sampler(unit) = binding {
+            self.active_texture(unit);
+        }
+        let object = binding.query();
+        self.bindings.insert(binding, object);
+        object
+    }
+
+    fn current_viewport(&mut self) -> ViewportRect {
+        *self.viewport.get_or_insert_with(|| {
+            let mut rect: [GLint; 4] = [0; 4];
+            crate::get_integerv(gl::VIEWPORT, &mut rect);
+            (rect[0], rect[1], rect[2], rect[3])
+        })
+    }
+
+    fn set_viewport(&mut self, x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
+        if self.viewport != Some((x, y, width, height)) {
+            crate::viewport(x, y, width, height);
+            self.viewport = Some((x, y, width, height));
+        }
+    }
+
+    /// Save the object bound to `binding` in the innermost scope, if the scope
+    /// has not saved it yet.
+    fn save(&mut self, binding: Binding) {
+        let saved = match self.scopes.last() {
+            Some(scope) => scope.bindings.iter().any(|(b, _)| *b == binding),
+            None => true,
+        };
+        if !saved {
+            let object = self.current(binding);
+            self.scopes
+                .last_mut()
+                .unwrap()
+                .bindings
+                .push((binding, object));
+        }
+    }
+
+    /// Bind `object` to `binding` if not bound yet.
+    fn set(&mut self, binding: Binding, object: GLuint) {
+        if self.bindings.get(&binding) != Some(&object) {
+            self.save(binding);
+            self.restore(binding, object);
+        }
+    }
+
+    /// Bind `object` to `binding` without saving the replaced object.
+    fn restore(&mut self, binding: Binding, object: GLuint) {
+        if self.bindings.get(&binding) == Some(&object) {
+            return;
+        }
+        if let Binding::Texture(unit, _) = binding {
+            self.active_texture(unit);
+        }
+        binding.apply(object);
+        self.bindings.insert(binding, object);
+        if binding == Binding::VertexArray {
+            // The element array binding belongs to the vertex array.
+            self.bindings
+                .remove(&Binding::Buffer(gl::ELEMENT_ARRAY_BUFFER));
+        }
+    }
+
+    /// Returns `false` if another object than `object` is known bound to `binding`.
+    fn is_bound(&self, binding: Binding, object: GLuint) -> bool {
+        match self.bindings.get(&binding) {
+            Some(bound) => *bound == object,
+            None => true,
+        }
+    }
+
+    /// Release `object` from `binding`, rebinds the object saved by the
+    /// innermost scope which changed the binding, or `0` outside the scopes.
+    fn release(&mut self, binding: Binding, object: GLuint) {
+        if !self.is_bound(binding, object) {
+            return;
+        }
+        let saved = self.scopes.iter().rev().find_map(|scope| {
+            scope
+                .bindings
+                .iter()
+                .find(|(b, _)| *b == binding)
+                .map(|(_, o)| *o)
+        });
+        self.set(binding, saved.unwrap_or(0));
+    }
+
+    /// Drop `object` from the cached and saved bindings matched by `filter`.
+    fn forget<F>(&mut self, object: GLuint, filter: F)
+    where
+        F: Fn(Binding) -> bool,
+    {
+        self.bindings.retain(|b, o| !(filter(*b) && *o == object));
+        for scope in self.scopes.iter_mut() {
+            for (b, o) in scope.bindings.iter_mut() {
+                if filter(*b) && *o == object {
+                    *o = 0;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    thread_local! {
+        static APPLIED: RefCell<Vec<(Binding, GLuint)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Record the binding applied instead of calling GL.
+    pub(super) fn record(binding: Binding, object: GLuint) {
+        APPLIED.with(|applied| applied.borrow_mut().push((binding, object)));
+    }
+
+    fn applied() -> Vec<(Binding, GLuint)> {
+        APPLIED.with(|applied| applied.borrow_mut().drain(..).collect())
+    }
+
+    /// Returns a cache which knows the default bindings of a fresh context.
+    fn fresh_cache() -> StateCache {
+        applied();
+        let mut cache = StateCache::new();
+        for binding in [
+            Binding::Program,
+            Binding::ActiveTexture,
+            Binding::Texture(0, gl::TEXTURE_2D),
+            Binding::Texture(1, gl::TEXTURE_2D),
+            Binding::Framebuffer(gl::DRAW_FRAMEBUFFER),
+            Binding::Framebuffer(gl::READ_FRAMEBUFFER),
+            Binding::Renderbuffer,
+            Binding::Sampler(0),
+            Binding::TransformFeedback,
+        ] {
+            cache.bindings.insert(binding, 0);
+        }
+        cache
+    }
+
+    #[test]
+    fn test_redundant_binds() {
+        let mut cache = fresh_cache();
+        cache.use_program(3);
+        cache.use_program(3);
+        cache.bind_renderbuffer(5);
+        cache.bind_renderbuffer(5);
+        cache.bind_sampler(0, 7);
+        cache.bind_sampler(0, 7);
+        assert_eq!(
+            applied(),
+            vec![
+                (Binding::Program, 3),
+                (Binding::Renderbuffer, 5),
+                (Binding::Sampler(0), 7),
+            ]
+        );
+        // The invalidated bindings are issued again.
+        cache.invalidate_renderbuffer();
+        cache.invalidate_sampler(0);
+        cache.bind_renderbuffer(5);
+        cache.bind_sampler(0, 7);
+        cache.use_program(3);
+        assert_eq!(
+            applied(),
+            vec![(Binding::Renderbuffer, 5), (Binding::Sampler(0), 7)]
+        );
+    }
+
+    #[test]
+    fn test_nested_scopes() {
+        let mut cache = fresh_cache();
+        let draw = Binding::Framebuffer(gl::DRAW_FRAMEBUFFER);
+        let unit1 = Binding::Texture(1, gl::TEXTURE_2D);
+        let outer = cache.begin_scope();
+        cache.bind_framebuffer(gl::FRAMEBUFFER, 1);
+        cache.use_program(2);
+        cache.bind_texture_at(1, gl::TEXTURE_2D, 3);
+        cache.bind_transform_feedback(4);
+
+        let inner = cache.begin_scope();
+        cache.bind_framebuffer(gl::FRAMEBUFFER, 5);
+        cache.use_program(6);
+        cache.bind_texture_at(1, gl::TEXTURE_2D, 7);
+        cache.bind_transform_feedback(8);
+        // Releasing inside the scope restores the object of the outer scope.
+        cache.unbind_program(6);
+        assert_eq!(cache.bindings[&Binding::Program], 2);
+        cache.unbind_transform_feedback(8);
+        assert_eq!(cache.bindings[&Binding::TransformFeedback], 4);
+
+        applied();
+        cache.end_scope(inner);
+        assert_eq!(cache.bindings[&draw], 1);
+        assert_eq!(cache.bindings[&unit1], 3);
+        assert_eq!(cache.bindings[&Binding::Program], 2);
+        let restored = applied();
+        assert!(restored.contains(&(Binding::Framebuffer(gl::DRAW_FRAMEBUFFER), 1)));
+        assert!(restored.contains(&(unit1, 3)));
+        assert!(!restored.iter().any(|(b, _)| *b == Binding::Program));
+
+        cache.end_scope(outer);
+        for binding in [draw, unit1, Binding::Program, Binding::TransformFeedback] {
+            assert_eq!(cache.bindings[&binding], 0);
+        }
+        assert!(cache.scopes.is_empty());
+        applied();
+        // Ending a scope again does nothing.
+        cache.end_scope(outer);
+        assert!(applied().is_empty());
+    }
+}
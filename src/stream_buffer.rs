@@ -0,0 +1,262 @@
+use crate::{gl, Buffer, Fence, FenceStatus, GLint, GLsizeiptr, MapAccess};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Default time `alloc()` waits for the GPU to release a region.
+pub const STREAM_BUFFER_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Region of a finished frame which the GPU may still read.
+#[derive(Debug)]
+struct FrameRegion<F> {
+    start: usize,
+    fence: F,
+}
+
+/// Positions of the ring, `head` and the starts of the frames grow
+/// monotonically and wrap by `capacity`.
+#[derive(Debug)]
+struct Ring<F> {
+    capacity: usize,
+    head: usize,
+    frame_start: usize,
+    frames: VecDeque<FrameRegion<F>>,
+}
+
+impl<F> Ring<F> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            head: 0,
+            frame_start: 0,
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Allocate `size` bytes aligned to `alignment`, `wait` blocks until the
+    /// fence of a frame overlapping the region is passed.
+    fn alloc<W>(&mut self, size: usize, alignment: usize, mut wait: W) -> Result<usize, String>
+    where
+        W: FnMut(&F) -> Result<(), String>,
+    {
+        let position = place(self.head, self.capacity, size, alignment)?;
+        let end = position + size;
+        if end > self.frame_start + self.capacity {
+            return Err(format!(
+                "Frame data exceeds the capacity {} of the stream buffer!",
+                self.capacity
+            ));
+        }
+        while let Some(frame) = self.frames.front() {
+            if end <= frame.start + self.capacity {
+                break;
+            }
+            wait(&frame.fence)?;
+            self.frames.pop_front();
+        }
+        self.head = end;
+        Ok(position % self.capacity)
+    }
+
+    /// Fence the regions allocated since the previous frame with `fence`.
+    fn end_frame<N>(&mut self, fence: N)
+    where
+        N: FnOnce() -> F,
+    {
+        if self.head > self.frame_start {
+            self.frames.push_back(FrameRegion {
+                start: self.frame_start,
+                fence: fence(),
+            });
+            self.frame_start = self.head;
+        }
+    }
+}
+
+/// Ring allocator for per-frame dynamic data on top of a Buffer.
+///
+/// Data is written into aligned regions of one buffer without re-specifying
+/// its storage, `end_frame()` fences the regions of the frame, and the regions
+/// are reused only after the GPU has passed the fence.
+#[derive(Debug)]
+pub struct StreamBuffer {
+    buffer: Buffer,
+    ring: Ring<Fence>,
+    uniform_alignment: usize,
+    wait_timeout: Duration,
+}
+
+impl StreamBuffer {
+    /// Create a ring of `capacity` bytes for `buffer_type` target.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn new(buffer_type: u32, capacity: usize) -> Self {
+        let buffer = Buffer::new(buffer_type);
+        crate::buffer_data::<u8>(buffer_type, capacity as GLsizeiptr, None, gl::STREAM_DRAW);
+        let mut alignment: [GLint; 1] = [0];
+        crate::get_integerv(gl::UNIFORM_BUFFER_OFFSET_ALIGNMENT, &mut alignment);
+        Self {
+            buffer,
+            ring: Ring::new(capacity),
+            uniform_alignment: alignment[0].max(1) as usize,
+            wait_timeout: STREAM_BUFFER_WAIT_TIMEOUT,
+        }
+    }
+
+    /// Set the time `alloc()` waits for the GPU to release a region.
+    pub fn with_wait_timeout(mut self, timeout: Duration) -> Self {
+        self.wait_timeout = timeout;
+        self
+    }
+
+    /// Set the time `alloc()` waits for the GPU to release a region.
+    pub fn set_wait_timeout(&mut self, timeout: Duration) {
+        self.wait_timeout = timeout;
+    }
+
+    /// Create a ring for vertex data.
+    pub fn new_array(capacity: usize) -> Self {
+        Self::new(gl::ARRAY_BUFFER, capacity)
+    }
+
+    /// Create a ring for index data.
+    pub fn new_element_array(capacity: usize) -> Self {
+        Self::new(gl::ELEMENT_ARRAY_BUFFER, capacity)
+    }
+
+    /// Create a ring for uniform blocks.
+    pub fn new_uniform(capacity: usize) -> Self {
+        Self::new(gl::UNIFORM_BUFFER, capacity)
+    }
+
+    /// Returns the underlying Buffer.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the size of the ring in bytes.
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity
+    }
+
+    /// Allocate `size` bytes aligned to `alignment`, returns the offset in the buffer.
+    ///
+    /// Waits for the GPU if the region is still used by a previous frame,
+    /// returns error if the GPU does not release it within the wait timeout
+    /// or the wait failed, e.g. the context is lost.
+    pub fn alloc(&mut self, size: usize, alignment: usize) -> Result<usize, String> {
+        let timeout = self.wait_timeout;
+        self.ring
+            .alloc(size, alignment, |fence| match fence.wait(timeout) {
+                FenceStatus::Signaled => Ok(()),
+                FenceStatus::TimeoutExpired => Err(format!(
+                    "GPU did not release the stream buffer region within {:?}!",
+                    timeout
+                )),
+                FenceStatus::Failed => Err("Wait for the stream buffer region failed!".to_string()),
+            })
+    }
+
+    /// Copy `data` into a region aligned to `alignment` bytes.
+    ///
+    /// Returns the buffer and the byte offset of the data.
+    pub fn push<T>(&mut self, data: &[T], alignment: usize) -> Result<(&Buffer, usize), String>
+    where
+        T: Sized + Copy,
+    {
+        let size = std::mem::size_of_val(data);
+        let offset = self.alloc(size, alignment.max(std::mem::align_of::<T>()))?;
+        let mut mapped = self.buffer.map_range::<u8>(
+            offset,
+            size,
+            MapAccess::WRITE | MapAccess::INVALIDATE_RANGE | MapAccess::UNSYNCHRONIZED,
+        )?;
+        let bytes = unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, size) };
+        mapped.write(0, bytes)?;
+        mapped.unmap()?;
+        Ok((&self.buffer, offset))
+    }
+
+    /// Copy vertex or index `data`, aligned to the element type.
+    pub fn push_data<T>(&mut self, data: &[T]) -> Result<(&Buffer, usize), String>
+    where
+        T: Sized + Copy,
+    {
+        self.push(data, std::mem::align_of::<T>())
+    }
+
+    /// Copy uniform block `data`, aligned to `GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT`.
+    pub fn push_uniform<T>(&mut self, data: &T) -> Result<(&Buffer, usize), String>
+    where
+        T: Sized + Copy,
+    {
+        let alignment = self.uniform_alignment;
+        self.push(std::slice::from_ref(data), alignment)
+    }
+
+    /// Fence the regions allocated since the previous frame.
+    pub fn end_frame(&mut self) {
+        self.ring.end_frame(Fence::new);
+    }
+}
+
+/// Returns the position of `size` bytes aligned to `alignment` in a ring of
+/// `capacity` bytes, which wraps to the next lap if it does not fit before the end.
+fn place(head: usize, capacity: usize, size: usize, alignment: usize) -> Result<usize, String> {
+    if size > capacity {
+        return Err(format!(
+            "Allocation of {} bytes exceeds the capacity {} of the stream buffer!",
+            size, capacity
+        ));
+    }
+    let alignment = alignment.max(1);
+    let lap = head - head % capacity;
+    let offset = (head % capacity).div_ceil(alignment) * alignment;
+    if offset + size > capacity {
+        Ok(lap + capacity)
+    } else {
+        Ok(lap + offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_place() {
+        assert_eq!(place(0, 1024, 100, 4), Ok(0));
+        assert_eq!(place(101, 1024, 16, 256), Ok(256));
+        assert_eq!(place(1000, 1024, 100, 4), Ok(1024));
+        assert_eq!(place(1024 + 10, 1024, 8, 16), Ok(1024 + 16));
+        assert!(place(0, 1024, 2048, 4).is_err());
+    }
+
+    #[test]
+    fn test_ring_reclaim() {
+        let mut ring = Ring::new(100);
+        let waited = std::cell::RefCell::new(Vec::new());
+        let signaled = |fence: &u32| {
+            waited.borrow_mut().push(*fence);
+            Ok(())
+        };
+        assert_eq!(ring.alloc(40, 1, &signaled), Ok(0));
+        assert_eq!(ring.alloc(40, 1, &signaled), Ok(40));
+        ring.end_frame(|| 1);
+        // Wraps to the start of the ring, reclaimed from the first frame.
+        assert_eq!(ring.alloc(30, 1, &signaled), Ok(0));
+        assert!(ring.frames.is_empty());
+        // The frame may not overrun its own start.
+        assert!(ring.alloc(60, 1, &signaled).is_err());
+        assert_eq!(ring.head, 130);
+        assert_eq!(*waited.borrow(), vec![1]);
+
+        ring.end_frame(|| 2);
+        ring.end_frame(|| 3);
+        assert_eq!(ring.frames.len(), 1);
+        let expired = |_: &u32| Err("expired".to_string());
+        assert_eq!(ring.alloc(60, 1, expired), Err("expired".to_string()));
+        // The frame stays fenced and the head is kept after the error.
+        assert_eq!(ring.frames.len(), 1);
+        assert_eq!(ring.head, 130);
+        assert_eq!(ring.alloc(40, 1, &signaled), Ok(30));
+    }
+}
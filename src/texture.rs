@@ -3,9 +3,9 @@
 use crate::GLeglImageOES;
 use crate::{
     gl,
-    gl::{GLenum, GLint, GLsizei, GLuint},
+    gl::{GLenum, GLint, GLintptr, GLsizei, GLuint},
     prelude::*,
-    Finalizer,
+    CompareFunc, CubeMapFace, CubeMapLayout, CubeMapTexture, Finalizer, PixelUnpack, StateCache,
 };
 use std::cell::Cell;
 use std::fmt::Debug;
@@ -14,6 +14,10 @@ use std::fmt::Debug;
 pub enum TextureFilter {
     Nearest = gl::NEAREST as isize,
     Linear = gl::LINEAR as isize,
+    NearestMipmapNearest = gl::NEAREST_MIPMAP_NEAREST as isize,
+    LinearMipmapNearest = gl::LINEAR_MIPMAP_NEAREST as isize,
+    NearestMipmapLinear = gl::NEAREST_MIPMAP_LINEAR as isize,
+    LinearMipmapLinear = gl::LINEAR_MIPMAP_LINEAR as isize,
 }
 
 impl Default for TextureFilter {
@@ -22,11 +26,25 @@ impl Default for TextureFilter {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TextureFormat {
+    // Unsized
     Alpha = gl::ALPHA as isize,
+    #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
     Luminance = gl::LUMINANCE as isize,
+    #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
     LuminanceAlpha = gl::LUMINANCE_ALPHA as isize,
+    Red = gl::RED as isize,
+    RedInteger = gl::RED_INTEGER as isize,
+    Rg = gl::RG as isize,
+    RgInteger = gl::RG_INTEGER as isize,
+    Rgb = gl::RGB as isize,
+    RgbInteger = gl::RGB_INTEGER as isize,
+    Rgba = gl::RGBA as isize,
+    RgbaInteger = gl::RGBA_INTEGER as isize,
+    DepthComponent = gl::DEPTH_COMPONENT as isize,
+    DepthStencil = gl::DEPTH_STENCIL as isize,
+    // Sized R
     R8 = gl::R8 as isize,
     R8SNorm = gl::R8_SNORM as isize,
     #[cfg(feature = "gl4")]
@@ -34,13 +52,430 @@ pub enum TextureFormat {
     R16F = gl::R16F as isize,
     #[cfg(feature = "gl4")]
     R16SNorm = gl::R16_SNORM as isize,
-    Red = gl::RED as isize,
-    Rg = gl::RG as isize,
-    Rgb = gl::RGB as isize,
+    R32F = gl::R32F as isize,
+    R8UI = gl::R8UI as isize,
+    R8I = gl::R8I as isize,
+    R16UI = gl::R16UI as isize,
+    R16I = gl::R16I as isize,
+    R32UI = gl::R32UI as isize,
+    R32I = gl::R32I as isize,
+    // Sized RG
+    Rg8 = gl::RG8 as isize,
+    Rg8SNorm = gl::RG8_SNORM as isize,
+    Rg16F = gl::RG16F as isize,
+    Rg32F = gl::RG32F as isize,
+    Rg8UI = gl::RG8UI as isize,
+    Rg8I = gl::RG8I as isize,
+    Rg16UI = gl::RG16UI as isize,
+    Rg16I = gl::RG16I as isize,
+    Rg32UI = gl::RG32UI as isize,
+    Rg32I = gl::RG32I as isize,
+    // Sized RGB
     Rgb8 = gl::RGB8 as isize,
-    Rgba = gl::RGBA as isize,
+    Srgb8 = gl::SRGB8 as isize,
+    Rgb565 = gl::RGB565 as isize,
+    Rgb8SNorm = gl::RGB8_SNORM as isize,
+    R11FG11FB10F = gl::R11F_G11F_B10F as isize,
+    Rgb9E5 = gl::RGB9_E5 as isize,
+    Rgb16F = gl::RGB16F as isize,
+    Rgb32F = gl::RGB32F as isize,
+    Rgb8UI = gl::RGB8UI as isize,
+    Rgb8I = gl::RGB8I as isize,
+    Rgb16UI = gl::RGB16UI as isize,
+    Rgb16I = gl::RGB16I as isize,
+    Rgb32UI = gl::RGB32UI as isize,
+    Rgb32I = gl::RGB32I as isize,
+    // Sized RGBA
+    Rgba8 = gl::RGBA8 as isize,
+    Srgb8Alpha8 = gl::SRGB8_ALPHA8 as isize,
+    Rgba8SNorm = gl::RGBA8_SNORM as isize,
+    Rgb5A1 = gl::RGB5_A1 as isize,
+    Rgba4 = gl::RGBA4 as isize,
+    Rgb10A2 = gl::RGB10_A2 as isize,
+    Rgb10A2UI = gl::RGB10_A2UI as isize,
+    Rgba16F = gl::RGBA16F as isize,
+    Rgba32F = gl::RGBA32F as isize,
+    Rgba8UI = gl::RGBA8UI as isize,
+    Rgba8I = gl::RGBA8I as isize,
+    Rgba16UI = gl::RGBA16UI as isize,
+    Rgba16I = gl::RGBA16I as isize,
+    Rgba32UI = gl::RGBA32UI as isize,
+    Rgba32I = gl::RGBA32I as isize,
+    // Sized depth and stencil
+    DepthComponent16 = gl::DEPTH_COMPONENT16 as isize,
+    DepthComponent24 = gl::DEPTH_COMPONENT24 as isize,
+    DepthComponent32F = gl::DEPTH_COMPONENT32F as isize,
+    Depth24Stencil8 = gl::DEPTH24_STENCIL8 as isize,
+    Depth32FStencil8 = gl::DEPTH32F_STENCIL8 as isize,
+}
+
+impl TextureFormat {
+    /// Returns true if the format is a sized internal format.
+    pub fn is_sized(self) -> bool {
+        self.external_format() != self
+    }
+
+    /// Returns true if the format contains depth or stencil components.
+    pub fn is_depth(self) -> bool {
+        matches!(
+            self.external_format(),
+            TextureFormat::DepthComponent | TextureFormat::DepthStencil
+        )
+    }
+
+    /// Returns the external format used to upload pixels to the format.
+    pub fn external_format(self) -> TextureFormat {
+        match self {
+            #[cfg(feature = "gl4")]
+            TextureFormat::R16 | TextureFormat::R16SNorm => TextureFormat::Red,
+            _ => TEXTURE_FORMATS
+                .iter()
+                .find(|(internal_format, _, _)| *internal_format == self)
+                .map_or(self, |(_, format, _)| *format),
+        }
+    }
+
+    /// Returns the default pixel type used to upload pixels to the format.
+    pub fn default_texel(self) -> TextureTexel {
+        match self {
+            #[cfg(feature = "gl4")]
+            TextureFormat::R16 => TextureTexel::U16,
+            #[cfg(feature = "gl4")]
+            TextureFormat::R16SNorm => TextureTexel::S16,
+            _ => TEXTURE_FORMATS
+                .iter()
+                .find(|(internal_format, _, _)| *internal_format == self)
+                .map_or(TextureTexel::U8, |(_, _, texel)| *texel),
+        }
+    }
+
+    /// Returns the number of components per pixel.
+    pub fn components(self) -> usize {
+        match self.external_format() {
+            #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+            TextureFormat::LuminanceAlpha => 2,
+            TextureFormat::Rg | TextureFormat::RgInteger | TextureFormat::DepthStencil => 2,
+            TextureFormat::Rgb | TextureFormat::RgbInteger => 3,
+            TextureFormat::Rgba | TextureFormat::RgbaInteger => 4,
+            _ => 1,
+        }
+    }
+
+    /// Returns the size in bytes of one pixel stored with `texel`.
+    pub fn pixel_size(self, texel: TextureTexel) -> usize {
+        if texel.is_packed() {
+            texel.size()
+        } else {
+            self.components() * texel.size()
+        }
+    }
+
+    /// Check the combination of `internal_format`, `format` and `texel` is
+    /// legal for glTexImage and glTexSubImage.
+    ///
+    /// # Note
+    /// Only OpenGL ES has a fixed table of combinations, any combination is
+    /// accepted for desktop OpenGL.
+    pub fn validate(
+        internal_format: TextureFormat,
+        format: TextureFormat,
+        texel: TextureTexel,
+    ) -> Result<(), String> {
+        if cfg!(not(any(
+            feature = "gles1",
+            feature = "gles2",
+            feature = "gles3"
+        ))) {
+            return Ok(());
+        }
+        let legal = if internal_format.is_sized() {
+            TEXTURE_FORMATS.contains(&(internal_format, format, texel))
+        } else {
+            internal_format == format && UNSIZED_TEXTURE_FORMATS.contains(&(format, texel))
+        };
+        if legal {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid texture format combination: {:?}, {:?}, {:?}!",
+                internal_format, format, texel
+            ))
+        }
+    }
 }
 
+/// Legal combinations of the sized internal format, format and texel type,
+/// see the table 3.2 of the OpenGL ES 3.0 specification.
+///
+/// The first combination of each internal format is the default one.
+const TEXTURE_FORMATS: &[(TextureFormat, TextureFormat, TextureTexel)] = &[
+    (TextureFormat::Rgba8, TextureFormat::Rgba, TextureTexel::U8),
+    (TextureFormat::Rgb5A1, TextureFormat::Rgba, TextureTexel::U8),
+    (
+        TextureFormat::Rgb5A1,
+        TextureFormat::Rgba,
+        TextureTexel::U16_5551,
+    ),
+    (
+        TextureFormat::Rgb5A1,
+        TextureFormat::Rgba,
+        TextureTexel::U32_2_10_10_10Rev,
+    ),
+    (TextureFormat::Rgba4, TextureFormat::Rgba, TextureTexel::U8),
+    (
+        TextureFormat::Rgba4,
+        TextureFormat::Rgba,
+        TextureTexel::U16_4444,
+    ),
+    (
+        TextureFormat::Srgb8Alpha8,
+        TextureFormat::Rgba,
+        TextureTexel::U8,
+    ),
+    (
+        TextureFormat::Rgba8SNorm,
+        TextureFormat::Rgba,
+        TextureTexel::S8,
+    ),
+    (
+        TextureFormat::Rgb10A2,
+        TextureFormat::Rgba,
+        TextureTexel::U32_2_10_10_10Rev,
+    ),
+    (
+        TextureFormat::Rgba16F,
+        TextureFormat::Rgba,
+        TextureTexel::F16,
+    ),
+    (
+        TextureFormat::Rgba16F,
+        TextureFormat::Rgba,
+        TextureTexel::F32,
+    ),
+    (
+        TextureFormat::Rgba32F,
+        TextureFormat::Rgba,
+        TextureTexel::F32,
+    ),
+    (
+        TextureFormat::Rgba8UI,
+        TextureFormat::RgbaInteger,
+        TextureTexel::U8,
+    ),
+    (
+        TextureFormat::Rgba8I,
+        TextureFormat::RgbaInteger,
+        TextureTexel::S8,
+    ),
+    (
+        TextureFormat::Rgba16UI,
+        TextureFormat::RgbaInteger,
+        TextureTexel::U16,
+    ),
+    (
+        TextureFormat::Rgba16I,
+        TextureFormat::RgbaInteger,
+        TextureTexel::S16,
+    ),
+    (
+        TextureFormat::Rgba32UI,
+        TextureFormat::RgbaInteger,
+        TextureTexel::U32,
+    ),
+    (
+        TextureFormat::Rgba32I,
+        TextureFormat::RgbaInteger,
+        TextureTexel::S32,
+    ),
+    (
+        TextureFormat::Rgb10A2UI,
+        TextureFormat::RgbaInteger,
+        TextureTexel::U32_2_10_10_10Rev,
+    ),
+    (TextureFormat::Rgb8, TextureFormat::Rgb, TextureTexel::U8),
+    (TextureFormat::Rgb565, TextureFormat::Rgb, TextureTexel::U8),
+    (
+        TextureFormat::Rgb565,
+        TextureFormat::Rgb,
+        TextureTexel::U16_565,
+    ),
+    (TextureFormat::Srgb8, TextureFormat::Rgb, TextureTexel::U8),
+    (
+        TextureFormat::Rgb8SNorm,
+        TextureFormat::Rgb,
+        TextureTexel::S8,
+    ),
+    (
+        TextureFormat::R11FG11FB10F,
+        TextureFormat::Rgb,
+        TextureTexel::U32_10F11F11FRev,
+    ),
+    (
+        TextureFormat::R11FG11FB10F,
+        TextureFormat::Rgb,
+        TextureTexel::F16,
+    ),
+    (
+        TextureFormat::R11FG11FB10F,
+        TextureFormat::Rgb,
+        TextureTexel::F32,
+    ),
+    (
+        TextureFormat::Rgb9E5,
+        TextureFormat::Rgb,
+        TextureTexel::U32_5_9_9_9Rev,
+    ),
+    (TextureFormat::Rgb9E5, TextureFormat::Rgb, TextureTexel::F16),
+    (TextureFormat::Rgb9E5, TextureFormat::Rgb, TextureTexel::F32),
+    (TextureFormat::Rgb16F, TextureFormat::Rgb, TextureTexel::F16),
+    (TextureFormat::Rgb16F, TextureFormat::Rgb, TextureTexel::F32),
+    (TextureFormat::Rgb32F, TextureFormat::Rgb, TextureTexel::F32),
+    (
+        TextureFormat::Rgb8UI,
+        TextureFormat::RgbInteger,
+        TextureTexel::U8,
+    ),
+    (
+        TextureFormat::Rgb8I,
+        TextureFormat::RgbInteger,
+        TextureTexel::S8,
+    ),
+    (
+        TextureFormat::Rgb16UI,
+        TextureFormat::RgbInteger,
+        TextureTexel::U16,
+    ),
+    (
+        TextureFormat::Rgb16I,
+        TextureFormat::RgbInteger,
+        TextureTexel::S16,
+    ),
+    (
+        TextureFormat::Rgb32UI,
+        TextureFormat::RgbInteger,
+        TextureTexel::U32,
+    ),
+    (
+        TextureFormat::Rgb32I,
+        TextureFormat::RgbInteger,
+        TextureTexel::S32,
+    ),
+    (TextureFormat::Rg8, TextureFormat::Rg, TextureTexel::U8),
+    (TextureFormat::Rg8SNorm, TextureFormat::Rg, TextureTexel::S8),
+    (TextureFormat::Rg16F, TextureFormat::Rg, TextureTexel::F16),
+    (TextureFormat::Rg16F, TextureFormat::Rg, TextureTexel::F32),
+    (TextureFormat::Rg32F, TextureFormat::Rg, TextureTexel::F32),
+    (
+        TextureFormat::Rg8UI,
+        TextureFormat::RgInteger,
+        TextureTexel::U8,
+    ),
+    (
+        TextureFormat::Rg8I,
+        TextureFormat::RgInteger,
+        TextureTexel::S8,
+    ),
+    (
+        TextureFormat::Rg16UI,
+        TextureFormat::RgInteger,
+        TextureTexel::U16,
+    ),
+    (
+        TextureFormat::Rg16I,
+        TextureFormat::RgInteger,
+        TextureTexel::S16,
+    ),
+    (
+        TextureFormat::Rg32UI,
+        TextureFormat::RgInteger,
+        TextureTexel::U32,
+    ),
+    (
+        TextureFormat::Rg32I,
+        TextureFormat::RgInteger,
+        TextureTexel::S32,
+    ),
+    (TextureFormat::R8, TextureFormat::Red, TextureTexel::U8),
+    (TextureFormat::R8SNorm, TextureFormat::Red, TextureTexel::S8),
+    (TextureFormat::R16F, TextureFormat::Red, TextureTexel::F16),
+    (TextureFormat::R16F, TextureFormat::Red, TextureTexel::F32),
+    (TextureFormat::R32F, TextureFormat::Red, TextureTexel::F32),
+    (
+        TextureFormat::R8UI,
+        TextureFormat::RedInteger,
+        TextureTexel::U8,
+    ),
+    (
+        TextureFormat::R8I,
+        TextureFormat::RedInteger,
+        TextureTexel::S8,
+    ),
+    (
+        TextureFormat::R16UI,
+        TextureFormat::RedInteger,
+        TextureTexel::U16,
+    ),
+    (
+        TextureFormat::R16I,
+        TextureFormat::RedInteger,
+        TextureTexel::S16,
+    ),
+    (
+        TextureFormat::R32UI,
+        TextureFormat::RedInteger,
+        TextureTexel::U32,
+    ),
+    (
+        TextureFormat::R32I,
+        TextureFormat::RedInteger,
+        TextureTexel::S32,
+    ),
+    (
+        TextureFormat::DepthComponent16,
+        TextureFormat::DepthComponent,
+        TextureTexel::U16,
+    ),
+    (
+        TextureFormat::DepthComponent16,
+        TextureFormat::DepthComponent,
+        TextureTexel::U32,
+    ),
+    (
+        TextureFormat::DepthComponent24,
+        TextureFormat::DepthComponent,
+        TextureTexel::U32,
+    ),
+    (
+        TextureFormat::DepthComponent32F,
+        TextureFormat::DepthComponent,
+        TextureTexel::F32,
+    ),
+    (
+        TextureFormat::Depth24Stencil8,
+        TextureFormat::DepthStencil,
+        TextureTexel::U32_24_8,
+    ),
+    (
+        TextureFormat::Depth32FStencil8,
+        TextureFormat::DepthStencil,
+        TextureTexel::F32U32_24_8Rev,
+    ),
+];
+
+/// Legal combinations of the unsized internal format and texel type.
+const UNSIZED_TEXTURE_FORMATS: &[(TextureFormat, TextureTexel)] = &[
+    (TextureFormat::Rgba, TextureTexel::U8),
+    (TextureFormat::Rgba, TextureTexel::U16_4444),
+    (TextureFormat::Rgba, TextureTexel::U16_5551),
+    (TextureFormat::Rgb, TextureTexel::U8),
+    (TextureFormat::Rgb, TextureTexel::U16_565),
+    #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+    (TextureFormat::LuminanceAlpha, TextureTexel::U8),
+    #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+    (TextureFormat::Luminance, TextureTexel::U8),
+    (TextureFormat::Alpha, TextureTexel::U8),
+];
+
 impl Default for TextureFormat {
     fn default() -> Self {
         TextureFormat::Rgba
@@ -54,16 +489,13 @@ pub enum TextureTarget {
     Texture3D = gl::TEXTURE_3D as isize,
     #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
     TextureExternalOES = gl::TEXTURE_EXTERNAL_OES as isize,
-    #[cfg(feature = "gl4")]
-    Texture3DArray = gl::TEXTURE_3D_ARRAY as isize,
+    TextureCubeMap = gl::TEXTURE_CUBE_MAP as isize,
     #[cfg(feature = "gl4")]
     ProxyTexture2D = gl::PROXY_TEXTURE_2D as isize,
     #[cfg(feature = "gl4")]
     ProxyTexture2DArray = gl::PROXY_TEXTURE_2D_ARRAY as isize,
     #[cfg(feature = "gl4")]
     ProxyTexture3D = gl::PROXY_TEXTURE_3D as isize,
-    #[cfg(feature = "gl4")]
-    ProxyTexture3DArray = gl::PROXY_TEXTURE_3D_ARRAY as isize,
     TextureCubeMapPositiveX = gl::TEXTURE_CUBE_MAP_POSITIVE_X as isize,
     TextureCubeMapNegativeX = gl::TEXTURE_CUBE_MAP_NEGATIVE_X as isize,
     TextureCubeMapPositiveY = gl::TEXTURE_CUBE_MAP_POSITIVE_Y as isize,
@@ -72,13 +504,23 @@ pub enum TextureTarget {
     TextureCubeMapNegativeZ = gl::TEXTURE_CUBE_MAP_NEGATIVE_Z as isize,
 }
 
+impl TextureTarget {
+    /// Returns true if the target has a depth or layers dimension.
+    pub fn is_layered(self) -> bool {
+        matches!(
+            self,
+            TextureTarget::Texture2DArray | TextureTarget::Texture3D
+        )
+    }
+}
+
 impl Default for TextureTarget {
     fn default() -> Self {
         TextureTarget::Texture2D
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TextureTexel {
     // Byte
     S8 = gl::BYTE as isize,
@@ -86,12 +528,13 @@ pub enum TextureTexel {
     #[cfg(feature = "gl4")]
     U8_332 = gl::UNSIGNED_BYTE_3_3_2 as isize,
     #[cfg(feature = "gl4")]
-    U8_233_REV = gl::UNSIGNED_BYTE_2_3_3_REV as isize,
+    U8_233Rev = gl::UNSIGNED_BYTE_2_3_3_REV as isize,
     // Float
     F16 = gl::HALF_FLOAT as isize,
     F32 = gl::FLOAT as isize,
     // Short
     S16 = gl::SHORT as isize,
+    U16 = gl::UNSIGNED_SHORT as isize,
     U16_565 = gl::UNSIGNED_SHORT_5_6_5 as isize,
     #[cfg(feature = "gl4")]
     U16_565Rev = gl::UNSIGNED_SHORT_5_6_5_REV as isize,
@@ -102,14 +545,56 @@ pub enum TextureTexel {
     #[cfg(feature = "gl4")]
     U16_1555Rev = gl::UNSIGNED_SHORT_1_5_5_5_REV as isize,
     // Int
+    S32 = gl::INT as isize,
+    U32 = gl::UNSIGNED_INT as isize,
     #[cfg(feature = "gl4")]
     U32_8888 = gl::UNSIGNED_INT_8_8_8_8 as isize,
     #[cfg(feature = "gl4")]
     U32_8888Rev = gl::UNSIGNED_INT_8_8_8_8_REV as isize,
     #[cfg(feature = "gl4")]
     U32_10_10_10_2 = gl::UNSIGNED_INT_10_10_10_2 as isize,
-    #[cfg(feature = "gl4")]
-    U32_2_10_10_10Rev = gl::GL_UNSIGNED_INT_2_10_10_10_REV as isize,
+    U32_2_10_10_10Rev = gl::UNSIGNED_INT_2_10_10_10_REV as isize,
+    U32_10F11F11FRev = gl::UNSIGNED_INT_10F_11F_11F_REV as isize,
+    U32_5_9_9_9Rev = gl::UNSIGNED_INT_5_9_9_9_REV as isize,
+    U32_24_8 = gl::UNSIGNED_INT_24_8 as isize,
+    F32U32_24_8Rev = gl::FLOAT_32_UNSIGNED_INT_24_8_REV as isize,
+}
+
+impl TextureTexel {
+    /// Returns true if all components are packed into a single value.
+    pub fn is_packed(self) -> bool {
+        !matches!(
+            self,
+            TextureTexel::S8
+                | TextureTexel::U8
+                | TextureTexel::F16
+                | TextureTexel::F32
+                | TextureTexel::S16
+                | TextureTexel::U16
+                | TextureTexel::S32
+                | TextureTexel::U32
+        )
+    }
+
+    /// Returns the size in bytes of one component, or of the whole pixel if packed.
+    pub fn size(self) -> usize {
+        match self {
+            TextureTexel::S8 | TextureTexel::U8 => 1,
+            TextureTexel::F32
+            | TextureTexel::S32
+            | TextureTexel::U32
+            | TextureTexel::U32_2_10_10_10Rev
+            | TextureTexel::U32_10F11F11FRev
+            | TextureTexel::U32_5_9_9_9Rev
+            | TextureTexel::U32_24_8 => 4,
+            TextureTexel::F32U32_24_8Rev => 8,
+            #[cfg(feature = "gl4")]
+            TextureTexel::U8_332 | TextureTexel::U8_233Rev => 1,
+            #[cfg(feature = "gl4")]
+            TextureTexel::U32_8888 | TextureTexel::U32_8888Rev | TextureTexel::U32_10_10_10_2 => 4,
+            _ => 2,
+        }
+    }
 }
 
 impl Default for TextureTexel {
@@ -135,6 +620,8 @@ impl Default for TextureWrap {
 pub struct TextureLoadOptions<'b> {
     path: Option<&'b str>,
     bytes: Option<&'b [u8]>,
+    faces: Option<[&'b [u8]; 6]>,
+    layout: Option<CubeMapLayout>,
     #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
     egl_image: Option<GLeglImageOES>,
     target: TextureTarget,
@@ -142,14 +629,18 @@ pub struct TextureLoadOptions<'b> {
     internal_format: TextureFormat,
     width: usize,
     height: usize,
+    depth: usize,
     format: TextureFormat,
     texel: TextureTexel,
     min_filter: TextureFilter,
     mag_filter: TextureFilter,
     wrap_s: TextureWrap,
     wrap_t: TextureWrap,
+    wrap_r: TextureWrap,
+    compare: Option<CompareFunc>,
     gen_mipmaps: bool,
     allocate_storage: bool,
+    storage_levels: usize,
 }
 
 impl<'b> TextureLoadOptions<'b> {
@@ -174,6 +665,18 @@ impl<'b> TextureLoadOptions<'b> {
             ..Default::default()
         }
     }
+
+    /// Returns the size of the texture image, which is the size of one face
+    /// if the cube map is loaded from a layout image.
+    fn image_size(&self) -> Result<(usize, usize), String> {
+        match self.layout {
+            Some(layout) => {
+                let size = layout.face_size(self.width, self.height)?;
+                Ok((size, size))
+            }
+            None => Ok((self.width, self.height)),
+        }
+    }
 }
 
 impl<'b> Default for TextureLoadOptions<'b> {
@@ -181,6 +684,8 @@ impl<'b> Default for TextureLoadOptions<'b> {
         Self {
             path: None,
             bytes: None,
+            faces: None,
+            layout: None,
             #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
             egl_image: None,
             target: TextureTarget::Texture2D,
@@ -188,14 +693,18 @@ impl<'b> Default for TextureLoadOptions<'b> {
             internal_format: TextureFormat::Rgba,
             width: 0,
             height: 0,
+            depth: 1,
             texel: TextureTexel::U8,
             format: TextureFormat::Rgba,
             min_filter: TextureFilter::Nearest,
             mag_filter: TextureFilter::Nearest,
             wrap_s: TextureWrap::ClampToEdge,
             wrap_t: TextureWrap::ClampToEdge,
+            wrap_r: TextureWrap::ClampToEdge,
+            compare: None,
             gen_mipmaps: false,
             allocate_storage: false,
+            storage_levels: 0,
         }
     }
 }
@@ -203,6 +712,8 @@ impl<'b> Default for TextureLoadOptions<'b> {
 pub struct TextureLoader<'a, 'b> {
     options: TextureLoadOptions<'b>,
     finalizer: Cell<Option<TextureFinalizer<'a>>>,
+    format_set: bool,
+    texel_set: bool,
 }
 
 impl<'a, 'b> TextureLoader<'a, 'b> {
@@ -210,6 +721,8 @@ impl<'a, 'b> TextureLoader<'a, 'b> {
         Self {
             options: Default::default(),
             finalizer: Cell::new(None),
+            format_set: false,
+            texel_set: false,
         }
     }
 
@@ -235,24 +748,71 @@ impl<'a, 'b> TextureLoader<'a, 'b> {
         self
     }
 
+    pub fn with_2d_array(&mut self) -> &mut Self {
+        self.options.target = TextureTarget::Texture2DArray;
+        self
+    }
+
+    pub fn with_cube_map(&mut self) -> &mut Self {
+        self.options.target = TextureTarget::TextureCubeMap;
+        self
+    }
+
+    /// Load the cube map from six face images ordered as `CubeMapFace::ALL`.
+    pub fn with_cube_map_faces(&mut self, faces: [&'b [u8]; 6]) -> &mut Self {
+        self.options.faces = Some(faces);
+        self.options.target = TextureTarget::TextureCubeMap;
+        self
+    }
+
+    /// Load the cube map from a single image with faces arranged in `layout`.
+    ///
+    /// The size given by `with_size` is the size of the whole image.
+    pub fn with_cube_map_layout(&mut self, bytes: &'b [u8], layout: CubeMapLayout) -> &mut Self {
+        self.options.bytes = Some(bytes);
+        self.options.layout = Some(layout);
+        self.options.target = TextureTarget::TextureCubeMap;
+        self
+    }
+
     pub fn with_size(&mut self, width: usize, height: usize) -> &mut Self {
         self.options.width = width;
         self.options.height = height;
         self
     }
 
+    /// Set the depth of 3D texture or the number of layers of 2D texture array.
+    pub fn with_depth(&mut self, depth: usize) -> &mut Self {
+        self.options.depth = depth;
+        self
+    }
+
+    /// Set the internal format of the texture.
+    ///
+    /// The format and texel not set by `with_format` and `with_texel` are
+    /// derived from sized internal formats, in any order of the calls.
     pub fn with_internal_format(&mut self, internal_format: TextureFormat) -> &mut Self {
         self.options.internal_format = internal_format;
+        if internal_format.is_sized() {
+            if !self.format_set {
+                self.options.format = internal_format.external_format();
+            }
+            if !self.texel_set {
+                self.options.texel = internal_format.default_texel();
+            }
+        }
         self
     }
 
     pub fn with_format(&mut self, format: TextureFormat) -> &mut Self {
         self.options.format = format;
+        self.format_set = true;
         self
     }
 
     pub fn with_texel(&mut self, texel: TextureTexel) -> &mut Self {
         self.options.texel = texel;
+        self.texel_set = true;
         self
     }
 
@@ -288,6 +848,22 @@ impl<'a, 'b> TextureLoader<'a, 'b> {
         self
     }
 
+    pub fn with_min_filter(&mut self, filter: TextureFilter) -> &mut Self {
+        self.options.min_filter = filter;
+        self
+    }
+
+    pub fn with_mag_filter(&mut self, filter: TextureFilter) -> &mut Self {
+        self.options.mag_filter = filter;
+        self
+    }
+
+    /// Enable depth comparison with `func` for sampling by `sampler2DShadow`.
+    pub fn with_compare(&mut self, func: CompareFunc) -> &mut Self {
+        self.options.compare = Some(func);
+        self
+    }
+
     pub fn with_gen_mipmaps(&mut self) -> &mut Self {
         self.options.gen_mipmaps = true;
         self
@@ -298,6 +874,25 @@ impl<'a, 'b> TextureLoader<'a, 'b> {
         self
     }
 
+    /// Allocate immutable storage with `levels` mipmap levels by glTexStorage.
+    ///
+    /// The internal format must be a sized format, contents are uploaded with
+    /// `glTexSubImage` afterwards.
+    pub fn with_immutable_storage(&mut self, levels: usize) -> &mut Self {
+        self.options.storage_levels = levels;
+        self
+    }
+
+    /// Set the mipmap level which the contents are uploaded to.
+    ///
+    /// The width and height are the size of the level, the base size of the
+    /// texture is derived from them. The other levels, including the level 0,
+    /// are uploaded with `Texture::upload_level()`.
+    pub fn with_level(&mut self, level: usize) -> &mut Self {
+        self.options.level = level;
+        self
+    }
+
     pub fn with_finalizer<F>(&mut self, finalizer: F) -> &mut Self
     where
         F: Fn(&Texture<'a>) + 'a,
@@ -306,9 +901,26 @@ impl<'a, 'b> TextureLoader<'a, 'b> {
         self
     }
 
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn load(&self) -> Result<Texture<'a>, String> {
         Texture::load(self.options, self.finalizer.replace(None))
     }
+
+    /// Load as cube map texture.
+    ///
+    /// Seamless filtering across the faces is enabled for the context, see
+    /// `CubeMapTexture::set_seamless()`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn load_cube_map(&self) -> Result<CubeMapTexture<'a>, String> {
+        match self.options.target {
+            TextureTarget::TextureCubeMap => {
+                let texture = CubeMapTexture::from(self.load()?);
+                CubeMapTexture::set_seamless(true);
+                Ok(texture)
+            }
+            _ => Err("Texture target is not a cube map!".to_string()),
+        }
+    }
 }
 
 type TextureFinalizer<'a> = Finalizer<'a, Texture<'a>>;
@@ -319,6 +931,11 @@ pub struct Texture<'a> {
     target: TextureTarget,
     width: usize,
     height: usize,
+    depth: usize,
+    levels: usize,
+    internal_format: TextureFormat,
+    format: TextureFormat,
+    texel: TextureTexel,
     finalizer: Option<TextureFinalizer<'a>>,
 }
 
@@ -329,6 +946,11 @@ impl<'a> Debug for Texture<'a> {
             .field("target", &self.target)
             .field("width", &self.width)
             .field("height", &self.height)
+            .field("depth", &self.depth)
+            .field("levels", &self.levels)
+            .field("internal_format", &self.internal_format)
+            .field("format", &self.format)
+            .field("texel", &self.texel)
             .finish()
     }
 }
@@ -338,6 +960,7 @@ impl<'a> Drop for Texture<'a> {
         if let Some(ref f) = self.finalizer.take() {
             (f)(self);
         }
+        StateCache::with(|cache| cache.forget_texture(self.id));
         crate::delete_textures(&[self.id]);
         self.id = 0;
     }
@@ -347,23 +970,51 @@ impl<'a> Texture<'a> {
     /// Construct a texture with loading options.
     /// # Note
     /// Auto bind the texture when loaded.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn load<'b>(
         options: TextureLoadOptions<'b>,
         finalizer: Option<TextureFinalizer<'a>>,
     ) -> Result<Texture<'a>, String> {
+        let (width, height) = options.image_size()?;
+        if options.storage_levels > 0 && !options.internal_format.is_sized() {
+            return Err(format!(
+                "Immutable storage requires a sized internal format, but got {:?}!",
+                options.internal_format
+            ));
+        }
+        if options.storage_levels > 0 && options.level >= options.storage_levels {
+            return Err(format!(
+                "Level {} out of range, storage has {} levels!",
+                options.level, options.storage_levels
+            ));
+        }
+        // The size of the options is the size of the level, keep the base size.
+        let depth = match options.target {
+            TextureTarget::Texture3D => base_size(options.depth, options.level),
+            _ => options.depth,
+        };
         let texture = Texture {
             id: crate::new_texture(),
             target: options.target,
-            width: options.width,
-            height: options.height,
+            width: base_size(width, options.level),
+            height: base_size(height, options.level),
+            depth,
+            levels: options.storage_levels,
+            internal_format: options.internal_format,
+            format: options.format,
+            texel: options.texel,
             finalizer,
         };
         texture.bind();
+        if texture.is_immutable() {
+            texture.allocate_immutable_storage();
+        }
         texture.update(options)?;
         Ok(texture)
     }
 
     /// Set min filter parameter of the Texture.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_min_filter(&self, filter: TextureFilter) {
         crate::tex_parameteri(
             self.target as GLenum,
@@ -373,6 +1024,7 @@ impl<'a> Texture<'a> {
     }
 
     /// Set mag filter parameter of the Texture.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_mag_filter(&self, filter: TextureFilter) {
         crate::tex_parameteri(
             self.target as GLenum,
@@ -382,6 +1034,7 @@ impl<'a> Texture<'a> {
     }
 
     /// Set filter parameters of the Texture.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_filters(&self, min_filter: TextureFilter, mag_filter: TextureFilter) {
         crate::tex_parameteri(
             self.target as GLenum,
@@ -396,53 +1049,94 @@ impl<'a> Texture<'a> {
     }
 
     /// Set wrap S parameter of the Texture.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_wrap_s(&self, wrap_s: TextureWrap) {
         crate::tex_parameteri(self.target as GLenum, gl::TEXTURE_WRAP_S, wrap_s as GLint);
     }
 
     /// Set wrap T parameter of the Texture.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_wrap_t(&self, wrap_t: TextureWrap) {
         crate::tex_parameteri(self.target as GLenum, gl::TEXTURE_WRAP_T, wrap_t as GLint);
     }
 
+    /// Set wrap R parameter of the Texture.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_wrap_r(&self, wrap_r: TextureWrap) {
+        crate::tex_parameteri(self.target as GLenum, gl::TEXTURE_WRAP_R, wrap_r as GLint);
+    }
+
     /// Set wrapping parameters of the Texture.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn set_wraps(&self, wrap_s: TextureWrap, wrap_t: TextureWrap) {
         crate::tex_parameteri(self.target as GLenum, gl::TEXTURE_WRAP_S, wrap_s as GLint);
         crate::tex_parameteri(self.target as GLenum, gl::TEXTURE_WRAP_T, wrap_t as GLint);
     }
 
+    /// Set the depth comparison function of the Texture, `None` disables the comparison.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_compare(&self, func: Option<CompareFunc>) {
+        match func {
+            Some(func) => {
+                crate::tex_parameteri(
+                    self.target as GLenum,
+                    gl::TEXTURE_COMPARE_MODE,
+                    gl::COMPARE_REF_TO_TEXTURE as GLint,
+                );
+                crate::tex_parameteri(
+                    self.target as GLenum,
+                    gl::TEXTURE_COMPARE_FUNC,
+                    func as GLint,
+                );
+            }
+            None => {
+                crate::tex_parameteri(
+                    self.target as GLenum,
+                    gl::TEXTURE_COMPARE_MODE,
+                    gl::NONE as GLint,
+                );
+            }
+        }
+    }
+
     /// Update contents and attributes with TextureLoadOptions.
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn update(&self, options: TextureLoadOptions<'_>) -> Result<(), String> {
         // crate::bind_texture(self.target as GLenum, self.id);
 
         // https://www.khronos.org/opengl/wiki/Common_Mistakes
 
+        if options.bytes.is_some() || options.faces.is_some() || options.allocate_storage {
+            TextureFormat::validate(options.internal_format, options.format, options.texel)?;
+        }
+
         self.set_filters(options.min_filter, options.mag_filter);
         self.set_wraps(options.wrap_s, options.wrap_t);
+        if options.compare.is_some() {
+            self.set_compare(options.compare);
+        }
 
-        if let Some(bytes) = options.bytes {
-            crate::tex_image2d(
+        if let TextureTarget::TextureCubeMap = self.target {
+            self.set_wrap_r(options.wrap_r);
+            self.update_cube_map(&options)?;
+        } else if self.target.is_layered() {
+            self.set_wrap_r(options.wrap_r);
+            self.update_layers(&options)?;
+        } else if let Some(bytes) = options.bytes {
+            self.upload_image2d(
                 self.target as GLenum,
-                options.level as GLint,
-                options.internal_format as GLint,
-                options.width as GLsizei,
-                options.height as GLsizei,
-                0,
-                options.format as GLuint,
-                options.texel as GLuint,
+                &options,
+                options.width,
+                options.height,
                 Some(bytes),
             );
         } else if options.allocate_storage {
-            crate::tex_image2d(
+            self.upload_image2d(
                 self.target as GLenum,
-                options.level as GLint,
-                options.internal_format as GLint,
-                options.width as GLsizei,
-                options.height as GLsizei,
-                0,
-                options.format as GLuint,
-                options.texel as GLuint,
-                None::<&[u8]>,
+                &options,
+                options.width,
+                options.height,
+                None,
             );
         }
 
@@ -462,43 +1156,626 @@ impl<'a> Texture<'a> {
         Ok(())
     }
 
+    /// Update contents of the region at (`x`, `y`) with size `width` x `height`.
+    ///
+    /// The layout of `bytes` is controlled by the `PixelUnpack` parameters binded
+    /// with the texture, so rows can be read directly from padded buffers.
+    /// # Note
+    /// Must be binded before call the routine.
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn update_region(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        format: TextureFormat,
+        texel: TextureTexel,
+        bytes: &[u8],
+    ) -> Result<(), String> {
+        self.check_region(x, y, width, height, format, texel)?;
+        let size = unpack_size(&PixelUnpack::current(), width, height, 1, format, texel);
+        if bytes.len() < size {
+            return Err(format!(
+                "Region requires {} bytes, but got {}!",
+                size,
+                bytes.len()
+            ));
+        }
+        crate::tex_sub_image2d(
+            self.target as GLenum,
+            0,
+            x as GLint,
+            y as GLint,
+            width as GLsizei,
+            height as GLsizei,
+            format as GLenum,
+            texel as GLenum,
+            bytes,
+        );
+        Ok(())
+    }
+
+    /// Update contents of the region from the binded `PIXEL_UNPACK_BUFFER`.
+    ///
+    /// The pixels are read from `offset` bytes of the buffer, the routine
+    /// returns as soon as the transfer is queued.
+    /// # Note
+    /// Both the texture and the pixel unpack buffer must be binded before call the routine.
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn update_region_from_buffer(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        format: TextureFormat,
+        texel: TextureTexel,
+        offset: usize,
+    ) -> Result<(), String> {
+        self.check_region(x, y, width, height, format, texel)?;
+        crate::tex_sub_image2d_offset(
+            self.target as GLenum,
+            0,
+            x as GLint,
+            y as GLint,
+            width as GLsizei,
+            height as GLsizei,
+            format as GLenum,
+            texel as GLenum,
+            offset as GLintptr,
+        );
+        Ok(())
+    }
+
+    /// Check the region is inside the texture and the format is compatible.
+    fn check_region(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        format: TextureFormat,
+        texel: TextureTexel,
+    ) -> Result<(), String> {
+        if x + width > self.width || y + height > self.height {
+            return Err(format!(
+                "Region {}x{}+{}+{} out of texture bounds {}x{}!",
+                width, height, x, y, self.width, self.height
+            ));
+        }
+        TextureFormat::validate(self.internal_format, format, texel)
+    }
+
+    /// Upload all layers of the 3D texture or 2D texture array with TextureLoadOptions.
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn update_layers(&self, options: &TextureLoadOptions<'_>) -> Result<(), String> {
+        let pixels = match options.bytes {
+            Some(bytes) => {
+                let size = unpack_size(
+                    &PixelUnpack::current(),
+                    options.width,
+                    options.height,
+                    options.depth,
+                    options.format,
+                    options.texel,
+                );
+                if bytes.len() < size {
+                    return Err(format!(
+                        "Texture requires {} bytes, but got {}!",
+                        size,
+                        bytes.len()
+                    ));
+                }
+                Some(bytes)
+            }
+            None if options.allocate_storage => None,
+            None => return Ok(()),
+        };
+        self.upload_image3d(
+            options,
+            options.width,
+            options.height,
+            options.depth,
+            pixels,
+        );
+        Ok(())
+    }
+
+    /// Upload contents of the mipmap `level` for precomputed mip chains.
+    ///
+    /// The size of the level is derived from the size of the base level.
+    /// # Note
+    /// Must be binded before call the routine.
+    pub fn upload_level(&self, level: usize, bytes: &[u8]) -> Result<(), String> {
+        if let TextureTarget::TextureCubeMap = self.target {
+            return Err("Use CubeMapTexture::update_face for the cube map levels!".to_string());
+        }
+        if self.is_immutable() && level >= self.levels {
+            return Err(format!(
+                "Level {} out of range, texture has {} levels!",
+                level, self.levels
+            ));
+        }
+        let width = level_size(self.width, level);
+        let height = level_size(self.height, level);
+        let depth = match self.target {
+            TextureTarget::Texture3D => level_size(self.depth, level),
+            _ => self.depth.max(1),
+        };
+        let layers = if self.target.is_layered() { depth } else { 1 };
+        let size = unpack_size(
+            &PixelUnpack::current(),
+            width,
+            height,
+            layers,
+            self.format,
+            self.texel,
+        );
+        if bytes.len() < size {
+            return Err(format!(
+                "Level {} requires {} bytes, but got {}!",
+                level,
+                size,
+                bytes.len()
+            ));
+        }
+        let options = TextureLoadOptions {
+            level,
+            internal_format: self.internal_format,
+            format: self.format,
+            texel: self.texel,
+            ..Default::default()
+        };
+        if self.target.is_layered() {
+            self.upload_image3d(&options, width, height, depth, Some(bytes));
+        } else {
+            self.upload_image2d(self.target as GLenum, &options, width, height, Some(bytes));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the storage is allocated by glTexStorage.
+    pub fn is_immutable(&self) -> bool {
+        self.levels > 0
+    }
+
+    /// Returns the number of mipmap levels of the immutable storage.
+    pub fn levels(&self) -> usize {
+        self.levels
+    }
+
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn allocate_immutable_storage(&self) {
+        if self.target.is_layered() {
+            crate::tex_storage3d(
+                self.target as GLenum,
+                self.levels as GLsizei,
+                self.internal_format as GLenum,
+                self.width as GLsizei,
+                self.height as GLsizei,
+                self.depth as GLsizei,
+            );
+        } else {
+            crate::tex_storage2d(
+                self.target as GLenum,
+                self.levels as GLsizei,
+                self.internal_format as GLenum,
+                self.width as GLsizei,
+                self.height as GLsizei,
+            );
+        }
+    }
+
+    /// Upload a 2D image to `target`, respecting the immutable storage.
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn upload_image2d(
+        &self,
+        target: GLenum,
+        options: &TextureLoadOptions<'_>,
+        width: usize,
+        height: usize,
+        bytes: Option<&[u8]>,
+    ) {
+        if !self.is_immutable() {
+            crate::tex_image2d(
+                target,
+                options.level as GLint,
+                options.internal_format as GLint,
+                width as GLsizei,
+                height as GLsizei,
+                0,
+                options.format as GLuint,
+                options.texel as GLuint,
+                bytes,
+            );
+        } else if let Some(bytes) = bytes {
+            crate::tex_sub_image2d(
+                target,
+                options.level as GLint,
+                0,
+                0,
+                width as GLsizei,
+                height as GLsizei,
+                options.format as GLuint,
+                options.texel as GLuint,
+                bytes,
+            );
+        }
+    }
+
+    /// Upload a 3D image, respecting the immutable storage.
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn upload_image3d(
+        &self,
+        options: &TextureLoadOptions<'_>,
+        width: usize,
+        height: usize,
+        depth: usize,
+        bytes: Option<&[u8]>,
+    ) {
+        if !self.is_immutable() {
+            crate::tex_image3d(
+                self.target as GLenum,
+                options.level as GLint,
+                options.internal_format as GLint,
+                width as GLsizei,
+                height as GLsizei,
+                depth as GLsizei,
+                0,
+                options.format as GLuint,
+                options.texel as GLuint,
+                bytes,
+            );
+        } else if let Some(bytes) = bytes {
+            crate::tex_sub_image3d(
+                self.target as GLenum,
+                options.level as GLint,
+                0,
+                0,
+                0,
+                width as GLsizei,
+                height as GLsizei,
+                depth as GLsizei,
+                options.format as GLuint,
+                options.texel as GLuint,
+                bytes,
+            );
+        }
+    }
+
+    /// Update contents of the `layer` of the 2D texture array or the slice of the 3D texture.
+    /// # Note
+    /// Must be binded before call the routine.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn update_layer(&self, layer: usize, bytes: &[u8]) -> Result<(), String> {
+        if !self.target.is_layered() {
+            return Err(format!("Texture target {:?} has no layers!", self.target));
+        }
+        if layer >= self.depth {
+            return Err(format!(
+                "Layer {} out of range, texture has {} layers!",
+                layer, self.depth
+            ));
+        }
+        let size = unpack_size(
+            &PixelUnpack::current(),
+            self.width,
+            self.height,
+            1,
+            self.format,
+            self.texel,
+        );
+        if bytes.len() < size {
+            return Err(format!(
+                "Layer requires {} bytes, but got {}!",
+                size,
+                bytes.len()
+            ));
+        }
+        crate::tex_sub_image3d(
+            self.target as GLenum,
+            0,
+            0,
+            0,
+            layer as GLint,
+            self.width as GLsizei,
+            self.height as GLsizei,
+            1,
+            self.format as GLenum,
+            self.texel as GLenum,
+            bytes,
+        );
+        Ok(())
+    }
+
+    /// Upload all faces of the cube map with TextureLoadOptions.
+    fn update_cube_map(&self, options: &TextureLoadOptions<'_>) -> Result<(), String> {
+        let (width, height) = options.image_size()?;
+        if let Some(faces) = options.faces {
+            let size = unpack_size(
+                &PixelUnpack::current(),
+                width,
+                height,
+                1,
+                options.format,
+                options.texel,
+            );
+            for (face, bytes) in CubeMapFace::ALL.iter().zip(faces.iter()) {
+                check_face_size(*face, size, bytes)?;
+            }
+            for (face, bytes) in CubeMapFace::ALL.iter().zip(faces.iter()) {
+                self.upload_image2d(*face as GLenum, options, width, height, Some(bytes));
+            }
+        } else if let Some(bytes) = options.bytes {
+            let layout = options
+                .layout
+                .ok_or_else(|| "Cube map requires six faces or a layout!".to_string())?;
+            let pixel_size = options.format.pixel_size(options.texel);
+            let faces = layout.extract_faces(bytes, options.width, options.height, pixel_size)?;
+            // The extracted faces are tightly packed.
+            let tight = PixelUnpack::new().with_alignment(1);
+            tight.bind();
+            for (face, bytes) in CubeMapFace::ALL.iter().zip(faces.iter()) {
+                self.upload_image2d(*face as GLenum, options, width, height, Some(bytes));
+            }
+            tight.unbind();
+        } else if options.allocate_storage {
+            for face in CubeMapFace::ALL.iter() {
+                self.upload_image2d(*face as GLenum, options, width, height, None);
+            }
+        }
+        Ok(())
+    }
+
+    /// Upload contents of the cube map `face` at the mipmap `level`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub(crate) fn upload_face(
+        &self,
+        face: CubeMapFace,
+        level: usize,
+        format: TextureFormat,
+        texel: TextureTexel,
+        bytes: &[u8],
+    ) -> Result<(), String> {
+        if self.is_immutable() && level >= self.levels {
+            return Err(format!(
+                "Level {} out of range, texture has {} levels!",
+                level, self.levels
+            ));
+        }
+        let size = level_size(self.width, level);
+        let required = unpack_size(&PixelUnpack::current(), size, size, 1, format, texel);
+        check_face_size(face, required, bytes)?;
+        let options = TextureLoadOptions {
+            level,
+            internal_format: self.internal_format,
+            format,
+            texel,
+            ..Default::default()
+        };
+        self.upload_image2d(face as GLenum, &options, size, size, Some(bytes));
+        Ok(())
+    }
+
     /// Update contents of the texture with EGLImage.
     /// # Note
     /// Must be binded before call the routine.
     #[cfg(any(feature = "gles1", feature = "gles2", feature = "gles3"))]
+    #[cfg_attr(feature = "checked", track_caller)]
     pub fn update_with_egl_image(&self, egl_image: GLeglImageOES) {
         // crate::bind_texture(self.target as GLenum, self.id);
         crate::egl_image_target_texture_2d_oes(self.target as GLenum, egl_image);
         // crate::bind_texture(self.target as GLenum, 0);
     }
 
+    /// Export level 0 of the 2D texture as an EGLImage.
+    ///
+    /// The image borrows the texture, so the texture must outlive the image and
+    /// every consumer made from it.
+    #[cfg(all(
+        unix,
+        feature = "egls",
+        any(feature = "gles1", feature = "gles2", feature = "gles3")
+    ))]
+    pub fn to_egl_image(&self) -> Result<crate::EglImage<'_>, String> {
+        crate::EglImage::from_texture(self)
+    }
+
     /// Returns the Id of the Texture.
     pub fn id(&self) -> GLuint {
         self.id
     }
 
+    /// Set the label of the Texture shown in the debug messages and the debuggers.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_label(&self, label: &str) {
+        crate::object_label(gl::TEXTURE, self.id(), label);
+    }
+
+    /// Returns the label of the Texture, empty if not labeled.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn label(&self) -> String {
+        crate::get_object_label(gl::TEXTURE, self.id())
+    }
+
     /// Returns the Target of the Texture.
     pub fn target(&self) -> TextureTarget {
         self.target
     }
+
+    /// Returns the width of the Texture.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the Texture.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the depth or the number of layers of the Texture.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the internal format of the Texture.
+    pub fn internal_format(&self) -> TextureFormat {
+        self.internal_format
+    }
 }
 
 impl<'a> Bindable for Texture<'a> {
     fn bind(&self) {
-        crate::bind_texture(self.target as GLenum, self.id);
+        StateCache::with(|cache| cache.bind_texture(self.target as GLenum, self.id));
     }
 
     fn bind_at(&self, index: u32) {
-        crate::active_texture(gl::TEXTURE0 + index);
-        self.bind();
+        StateCache::with(|cache| cache.bind_texture_at(index, self.target as GLenum, self.id));
     }
 
     fn unbind(&self) {
-        crate::bind_texture(self.target as GLenum, 0);
+        StateCache::with(|cache| cache.unbind_texture(self.target as GLenum, self.id));
+    }
+
+    fn unbind_at(&self, slot: u32) {
+        StateCache::with(|cache| cache.unbind_texture_at(slot, self.target as GLenum, self.id));
+    }
+}
+
+/// Returns the number of bytes read when uploading `depth` images of
+/// `width` x `height` pixels with the `unpack` parameters.
+pub(crate) fn unpack_size(
+    unpack: &PixelUnpack,
+    width: usize,
+    height: usize,
+    depth: usize,
+    format: TextureFormat,
+    texel: TextureTexel,
+) -> usize {
+    unpack.required_size_3d(width, height, depth, format.pixel_size(texel))
+}
+
+/// Returns the size of the mipmap `level` of the `base` size.
+fn level_size(base: usize, level: usize) -> usize {
+    (base >> level).max(1)
+}
+
+/// Returns the base size of the texture whose mipmap `level` has the `size`.
+fn base_size(size: usize, level: usize) -> usize {
+    size << level
+}
+
+/// Check the `bytes` of the cube map `face` hold at least `size` bytes.
+fn check_face_size(face: CubeMapFace, size: usize, bytes: &[u8]) -> Result<(), String> {
+    if bytes.len() < size {
+        return Err(format!(
+            "Cube map face {:?} requires {} bytes, but got {}!",
+            face,
+            size,
+            bytes.len()
+        ));
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn unbind_at(&self, _slot: u32) {
-        self.unbind();
-        crate::active_texture(gl::TEXTURE0);
+    #[test]
+    fn test_derive_external_format() {
+        assert_eq!(TextureFormat::Rgba8.external_format(), TextureFormat::Rgba);
+        assert_eq!(TextureFormat::Rgba16F.default_texel(), TextureTexel::F16);
+        assert_eq!(
+            TextureFormat::Rg8UI.external_format(),
+            TextureFormat::RgInteger
+        );
+        assert_eq!(TextureFormat::Rgba.external_format(), TextureFormat::Rgba);
+        assert!(TextureFormat::DepthComponent24.is_depth());
+        assert!(!TextureFormat::Rgba.is_sized());
+        assert_eq!(TextureFormat::Rgba32F.pixel_size(TextureTexel::F32), 16);
+    }
+
+    #[test]
+    fn test_validate() {
+        let validate = TextureFormat::validate;
+        assert!(validate(TextureFormat::Rgba8, TextureFormat::Rgba, TextureTexel::U8).is_ok());
+        assert!(validate(
+            TextureFormat::Rgba16F,
+            TextureFormat::Rgba,
+            TextureTexel::F32
+        )
+        .is_ok());
+        assert!(validate(TextureFormat::Rgba, TextureFormat::Rgba, TextureTexel::U8).is_ok());
+        // Desktop GL converts any combination, only GLES rejects them.
+        if cfg!(any(feature = "gles1", feature = "gles2", feature = "gles3")) {
+            assert!(
+                validate(TextureFormat::Rgba8, TextureFormat::Rgba, TextureTexel::F32).is_err()
+            );
+            assert!(validate(TextureFormat::Rgba, TextureFormat::Rgb, TextureTexel::U8).is_err());
+            assert!(validate(TextureFormat::R8UI, TextureFormat::Red, TextureTexel::U8).is_err());
+        }
+    }
+
+    #[test]
+    fn test_unpack_size() {
+        let (format, texel) = (TextureFormat::Rgb, TextureTexel::U8);
+        assert_eq!(
+            unpack_size(&PixelUnpack::new(), 3, 2, 1, format, texel),
+            12 + 9
+        );
+        // Rows of 5 pixels padded from 15 to 16 bytes.
+        let unpack = PixelUnpack::new().with_row_length(5).with_alignment(4);
+        assert_eq!(unpack_size(&unpack, 3, 2, 1, format, texel), 16 + 9);
+        assert!(unpack_size(&unpack, 3, 2, 1, format, texel) > 3 * 2 * 3);
+        assert_eq!(
+            unpack_size(&unpack, 3, 2, 2, format, texel),
+            2 * 16 + 16 + 9
+        );
+    }
+
+    #[test]
+    fn test_level_size() {
+        let sizes: Vec<usize> = (0..6).map(|level| level_size(20, level)).collect();
+        assert_eq!(sizes, vec![20, 10, 5, 2, 1, 1]);
+        // Loading the level 2 of 5 pixels derives the base of 20 pixels.
+        let base = base_size(5, 2);
+        assert_eq!(base, 20);
+        assert_eq!(level_size(base, 2), 5);
+        assert_eq!(level_size(base, 3), 2);
+        assert_eq!(base_size(5, 0), 5);
+    }
+
+    #[test]
+    fn test_check_face_size() {
+        // RGB faces of odd width are padded to the default alignment of 4.
+        let size = unpack_size(
+            &PixelUnpack::new(),
+            3,
+            3,
+            1,
+            TextureFormat::Rgb,
+            TextureTexel::U8,
+        );
+        assert_eq!(size, 2 * 12 + 9);
+        assert!(check_face_size(CubeMapFace::PositiveX, size, &[0; 3 * 3 * 3]).is_err());
+        assert!(check_face_size(CubeMapFace::PositiveX, size, &[0; 2 * 12 + 9]).is_ok());
+    }
+
+    #[test]
+    fn test_loader_format_order() {
+        let mut a = TextureLoader::default();
+        a.with_format(TextureFormat::Rgb)
+            .with_internal_format(TextureFormat::Rgba16F);
+        let mut b = TextureLoader::default();
+        b.with_internal_format(TextureFormat::Rgba16F)
+            .with_format(TextureFormat::Rgb);
+        for loader in [&a, &b].iter() {
+            assert_eq!(loader.options.format, TextureFormat::Rgb);
+            assert_eq!(loader.options.texel, TextureTexel::F16);
+        }
     }
 }
@@ -0,0 +1,189 @@
+use crate::{
+    gl, prelude::*, AutoBinder, Buffer, GLenum, GLintptr, GLsizeiptr, GLuint, Query, StateCache,
+};
+
+/// Capture modes of the transform feedback varyings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeedbackMode {
+    /// All varyings are written interleaved into the buffer at index 0.
+    Interleaved = gl::INTERLEAVED_ATTRIBS as isize,
+    /// Each varying is written into the buffer at its own index.
+    Separate = gl::SEPARATE_ATTRIBS as isize,
+}
+
+/// Transform feedback object which captures the vertex shader outputs into
+/// Buffers.
+///
+/// The varyings to capture are configured when the Program is built, see
+/// `Program::from_sources_with_varyings()`.
+#[derive(Debug)]
+pub struct TransformFeedback {
+    id: GLuint,
+    query: Query,
+}
+
+impl TransformFeedback {
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn new() -> Self {
+        Self {
+            id: crate::new_transform_feedback(),
+            query: Query::new_primitives_written(),
+        }
+    }
+
+    /// Returns the Id of the transform feedback object.
+    pub fn id(&self) -> GLuint {
+        self.id
+    }
+
+    /// Capture the outputs at `index` into the whole `buffer`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_buffer(&self, index: GLuint, buffer: &Buffer) {
+        // The transform feedback object of the caller is restored.
+        let _a = AutoBinder::new(vec![self]);
+        crate::bind_buffer_base(gl::TRANSFORM_FEEDBACK_BUFFER, index, buffer.id());
+        // The generic binding point is changed too.
+        StateCache::with(|cache| cache.invalidate_buffer(gl::TRANSFORM_FEEDBACK_BUFFER));
+    }
+
+    /// Capture the outputs at `index` into `size` bytes of `buffer` from `offset`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_buffer_range(&self, index: GLuint, buffer: &Buffer, offset: usize, size: usize) {
+        let _a = AutoBinder::new(vec![self]);
+        crate::bind_buffer_range(
+            gl::TRANSFORM_FEEDBACK_BUFFER,
+            index,
+            buffer.id(),
+            offset as GLintptr,
+            size as GLsizeiptr,
+        );
+        StateCache::with(|cache| cache.invalidate_buffer(gl::TRANSFORM_FEEDBACK_BUFFER));
+    }
+
+    /// Begin capturing `primitive_mode` primitives, which must be one of
+    /// `GL_POINTS`, `GL_LINES` or `GL_TRIANGLES`.
+    ///
+    /// The capture ends when the returned scope dropped.
+    pub fn begin(&self, primitive_mode: GLenum) -> FeedbackScope<'_> {
+        FeedbackScope::new(self, primitive_mode, false)
+    }
+
+    /// Begin capturing like `begin()`, with the rasterization disabled, for
+    /// passes like particle simulation which only need the captured outputs.
+    pub fn begin_discard(&self, primitive_mode: GLenum) -> FeedbackScope<'_> {
+        FeedbackScope::new(self, primitive_mode, true)
+    }
+
+    /// Returns the number of primitives written in the latest scope if
+    /// available, never blocks.
+    pub fn try_primitives_written(&self) -> Option<u64> {
+        self.query.try_result()
+    }
+
+    /// Returns the number of primitives written in the latest scope, blocks
+    /// until the result is available.
+    pub fn primitives_written(&self) -> u64 {
+        self.query.result()
+    }
+}
+
+impl Default for TransformFeedback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TransformFeedback {
+    fn drop(&mut self) {
+        StateCache::with(|cache| cache.forget_transform_feedback(self.id));
+        crate::delete_transform_feedbacks(&[self.id]);
+    }
+}
+
+impl Bindable for TransformFeedback {
+    fn bind(&self) {
+        StateCache::with(|cache| cache.bind_transform_feedback(self.id));
+    }
+
+    fn unbind(&self) {
+        StateCache::with(|cache| cache.unbind_transform_feedback(self.id));
+    }
+}
+
+/// Active capture of a TransformFeedback, ends the capture when dropped.
+///
+/// The transform feedback object and the rasterizer discard state of the
+/// caller are restored when dropped.
+#[derive(Debug)]
+pub struct FeedbackScope<'a> {
+    feedback: &'a TransformFeedback,
+    scope: usize,
+    discard: Option<bool>,
+}
+
+impl<'a> FeedbackScope<'a> {
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn new(feedback: &'a TransformFeedback, primitive_mode: GLenum, discard: bool) -> Self {
+        let scope = StateCache::with(|cache| cache.begin_scope());
+        feedback.bind();
+        let discard = if discard {
+            StateCache::with(|cache| {
+                let previous = cache.is_enabled(gl::RASTERIZER_DISCARD);
+                cache.set_enabled(gl::RASTERIZER_DISCARD, true);
+                Some(previous)
+            })
+        } else {
+            None
+        };
+        feedback.query.begin();
+        crate::begin_transform_feedback(primitive_mode);
+        Self {
+            feedback,
+            scope,
+            discard,
+        }
+    }
+
+    /// Pause the capture until the returned guard dropped, the draws in
+    /// between are not captured.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn pause(&self) -> FeedbackPause<'_> {
+        crate::pause_transform_feedback();
+        FeedbackPause { _scope: self }
+    }
+}
+
+impl<'a> Drop for FeedbackScope<'a> {
+    fn drop(&mut self) {
+        crate::end_transform_feedback();
+        self.feedback.query.end();
+        if let Some(previous) = self.discard {
+            StateCache::with(|cache| cache.set_enabled(gl::RASTERIZER_DISCARD, previous));
+        }
+        self.feedback.unbind();
+        StateCache::with(|cache| cache.end_scope(self.scope));
+    }
+}
+
+/// Paused capture of a FeedbackScope, resumes the capture when dropped.
+#[derive(Debug)]
+pub struct FeedbackPause<'a> {
+    _scope: &'a FeedbackScope<'a>,
+}
+
+impl<'a> Drop for FeedbackPause<'a> {
+    fn drop(&mut self) {
+        crate::resume_transform_feedback();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feedback_mode() {
+        assert_eq!(FeedbackMode::Interleaved as GLenum, gl::INTERLEAVED_ATTRIBS);
+        assert_eq!(FeedbackMode::Separate as GLenum, gl::SEPARATE_ATTRIBS);
+    }
+}
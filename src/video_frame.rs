@@ -0,0 +1,410 @@
+use crate::{
+    gl, prelude::*, AutoBinder, Buffer, Framebuffer, GLfloat, GLint, GLsizeiptr, GLuint,
+    PixelUnpack, Program, SlotBinder, Texture, TextureFormat, TextureLoader, VertexArray,
+    VertexAttrib, Viewport,
+};
+
+/// Pixel formats of the video frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VideoFormat {
+    /// Y plane followed by interleaved UV plane, 4:2:0.
+    Nv12,
+    /// Y plane followed by interleaved VU plane, 4:2:0.
+    Nv21,
+    /// Y, U and V planes, 4:2:0.
+    I420,
+    /// Packed Y0 U Y1 V, 4:2:2.
+    Yuyv,
+}
+
+impl VideoFormat {
+    /// Returns the number of planes of the frame data.
+    pub fn num_planes(self) -> usize {
+        match self {
+            VideoFormat::Nv12 | VideoFormat::Nv21 => 2,
+            VideoFormat::I420 => 3,
+            VideoFormat::Yuyv => 1,
+        }
+    }
+
+    /// Returns the row size in bytes of the `plane` for a frame of `width` pixels.
+    pub fn plane_stride(self, plane: usize, width: usize) -> usize {
+        let chroma_width = width.div_ceil(2);
+        match (self, plane) {
+            (VideoFormat::Nv12, 1) | (VideoFormat::Nv21, 1) => chroma_width * 2,
+            (VideoFormat::I420, 1) | (VideoFormat::I420, 2) => chroma_width,
+            (VideoFormat::Yuyv, _) => chroma_width * 4,
+            _ => width,
+        }
+    }
+
+    /// Returns the textures used to sample the frame, as tuples of the
+    /// source plane, texture format, width and height.
+    fn textures(self, width: usize, height: usize) -> Vec<(usize, TextureFormat, usize, usize)> {
+        let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+        match self {
+            VideoFormat::Nv12 | VideoFormat::Nv21 => vec![
+                (0, TextureFormat::R8, width, height),
+                (1, TextureFormat::Rg8, chroma_width, chroma_height),
+            ],
+            VideoFormat::I420 => vec![
+                (0, TextureFormat::R8, width, height),
+                (1, TextureFormat::R8, chroma_width, chroma_height),
+                (2, TextureFormat::R8, chroma_width, chroma_height),
+            ],
+            // Luma from the RG texels, chroma from the RGBA texels of the same plane.
+            VideoFormat::Yuyv => vec![
+                (0, TextureFormat::Rg8, width, height),
+                (0, TextureFormat::Rgba8, chroma_width, height),
+            ],
+        }
+    }
+
+    /// Returns the GLSL snippet which samples the `yuv` vector.
+    fn sample_source(self) -> &'static str {
+        match self {
+            VideoFormat::Nv12 => {
+                "yuv = vec3(texture2D(u_plane0, v_uv).r, texture2D(u_plane1, v_uv).rg);"
+            }
+            VideoFormat::Nv21 => {
+                "yuv = vec3(texture2D(u_plane0, v_uv).r, texture2D(u_plane1, v_uv).gr);"
+            }
+            VideoFormat::I420 => {
+                "yuv = vec3(texture2D(u_plane0, v_uv).r, \
+                            texture2D(u_plane1, v_uv).r, \
+                            texture2D(u_plane2, v_uv).r);"
+            }
+            VideoFormat::Yuyv => {
+                "yuv = vec3(texture2D(u_plane0, v_uv).r, texture2D(u_plane1, v_uv).ga);"
+            }
+        }
+    }
+}
+
+/// Matrix coefficients of the YUV to RGB conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YuvMatrix {
+    Bt601,
+    Bt709,
+}
+
+/// Value range of the YUV components.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YuvRange {
+    /// Y in [0, 255], UV in [0, 255].
+    Full,
+    /// Y in [16, 235], UV in [16, 240].
+    Limited,
+}
+
+/// Returns the column major 3x3 matrix and offset which convert normalized
+/// YUV to RGB with `rgb = matrix * (yuv - offset)`.
+pub fn yuv_to_rgb(matrix: YuvMatrix, range: YuvRange) -> ([GLfloat; 9], [GLfloat; 3]) {
+    let (kr, kb) = match matrix {
+        YuvMatrix::Bt601 => (0.299, 0.114),
+        YuvMatrix::Bt709 => (0.2126, 0.0722),
+    };
+    let kg = 1.0 - kr - kb;
+    let (y_scale, c_scale, y_offset) = match range {
+        YuvRange::Full => (1.0, 1.0, 0.0),
+        YuvRange::Limited => (255.0 / 219.0, 255.0 / 224.0, 16.0 / 255.0),
+    };
+    let rv = 2.0 * (1.0 - kr) * c_scale;
+    let gu = -2.0 * kb * (1.0 - kb) / kg * c_scale;
+    let gv = -2.0 * kr * (1.0 - kr) / kg * c_scale;
+    let bu = 2.0 * (1.0 - kb) * c_scale;
+    (
+        [y_scale, y_scale, y_scale, 0.0, gu, bu, rv, gv, 0.0],
+        [y_offset, 128.0 / 255.0, 128.0 / 255.0],
+    )
+}
+
+const VIDEO_FRAME_VERT: &str = "#version 100
+attribute vec2 a_position;
+attribute vec2 a_texcoord;
+varying vec2 v_uv;
+
+void main()
+{
+    v_uv = a_texcoord;
+    gl_Position = vec4(a_position, 0.0, 1.0);
+}
+";
+
+const VIDEO_FRAME_FRAG: &str = "#version 100
+precision mediump float;
+uniform sampler2D u_plane0;
+uniform sampler2D u_plane1;
+uniform sampler2D u_plane2;
+uniform mat3 u_matrix;
+uniform vec3 u_offset;
+varying vec2 v_uv;
+
+void main()
+{
+    vec3 yuv;
+    SAMPLE_YUV
+    gl_FragColor = vec4(clamp(u_matrix * (yuv - u_offset), 0.0, 1.0), 1.0);
+}
+";
+
+/// Planar or packed YUV video frame uploaded as R8/RG8 textures, with
+/// built-in conversion to RGB.
+#[derive(Debug)]
+pub struct VideoFrameTexture<'a> {
+    format: VideoFormat,
+    width: usize,
+    height: usize,
+    textures: Vec<(usize, Texture<'a>)>,
+    program: Program,
+    vao: VertexArray,
+    _vbo: Buffer,
+}
+
+impl<'a> VideoFrameTexture<'a> {
+    /// Create textures and conversion shaders for frames of `format` with
+    /// `width` x `height` pixels, converted with BT.601 limited range.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn new(format: VideoFormat, width: usize, height: usize) -> Result<Self, String> {
+        let mut textures = Vec::new();
+        for (plane, texture_format, w, h) in format.textures(width, height) {
+            let texture = TextureLoader::default()
+                .with_size(w, h)
+                .with_internal_format(texture_format)
+                .with_linear()
+                .with_allocate_storage()
+                .load()?;
+            texture.unbind();
+            textures.push((plane, texture));
+        }
+
+        let frag = VIDEO_FRAME_FRAG.replace("SAMPLE_YUV", format.sample_source());
+        let program = Program::from_sources(&[
+            (VIDEO_FRAME_VERT, gl::VERTEX_SHADER),
+            (frag.as_str(), gl::FRAGMENT_SHADER),
+        ])?;
+        for i in 0..3 {
+            if let Ok(loc) = program.locate_uniform(format!("u_plane{}", i)) {
+                crate::uniform1i(loc, i);
+            }
+        }
+        let position = program
+            .locate_attrib("a_position")
+            .map_err(|e| e.to_string())?;
+        let texcoord = program
+            .locate_attrib("a_texcoord")
+            .map_err(|e| e.to_string())?;
+
+        let vertices: [GLfloat; 16] = [
+            // positions  // texcoords
+            -1.0, -1.0, 0.0, 1.0, // bottom left
+            1.0, -1.0, 1.0, 1.0, // bottom right
+            -1.0, 1.0, 0.0, 0.0, // top left
+            1.0, 1.0, 1.0, 0.0, // top right
+        ];
+        let vao = VertexArray::new();
+        vao.bind();
+        let vbo = Buffer::new_array();
+        vbo.static_draw_data(&vertices[..]);
+        let stride = (4 * std::mem::size_of::<GLfloat>()) as GLint;
+        vao.enable_attrib(&VertexAttrib::new(
+            position as GLuint,
+            2,
+            gl::FLOAT,
+            gl::FALSE,
+            stride,
+            0,
+        ));
+        vao.enable_attrib(&VertexAttrib::new(
+            texcoord as GLuint,
+            2,
+            gl::FLOAT,
+            gl::FALSE,
+            stride,
+            (2 * std::mem::size_of::<GLfloat>()) as GLsizeiptr,
+        ));
+        vao.unbind();
+        vbo.unbind();
+
+        let video_frame = Self {
+            format,
+            width,
+            height,
+            textures,
+            program,
+            vao,
+            _vbo: vbo,
+        };
+        video_frame.set_color_space(YuvMatrix::Bt601, YuvRange::Limited);
+        Ok(video_frame)
+    }
+
+    /// Set the YUV to RGB conversion used by `draw_to`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_color_space(&self, matrix: YuvMatrix, range: YuvRange) {
+        let (matrix, offset) = yuv_to_rgb(matrix, range);
+        self.program.bind();
+        if let Ok(loc) = self.program.locate_uniform("u_matrix") {
+            crate::uniform_matrix3fv(loc, gl::FALSE, &matrix);
+        }
+        if let Ok(loc) = self.program.locate_uniform("u_offset") {
+            crate::uniform3f(loc, offset[0], offset[1], offset[2]);
+        }
+        self.program.unbind();
+    }
+
+    /// Upload the tightly packed `planes` of a frame.
+    pub fn update(&self, planes: &[&[u8]]) -> Result<(), String> {
+        let strides: Vec<usize> = (0..planes.len())
+            .map(|i| self.format.plane_stride(i, self.width))
+            .collect();
+        self.update_with_strides(planes, &strides)
+    }
+
+    /// Upload the `planes` of a frame with rows of `strides` bytes, as
+    /// produced by decoders with padded buffers.
+    pub fn update_with_strides(&self, planes: &[&[u8]], strides: &[usize]) -> Result<(), String> {
+        if planes.len() < self.format.num_planes() || strides.len() < planes.len() {
+            return Err(format!(
+                "{:?} frame requires {} planes with strides, but got {}!",
+                self.format,
+                self.format.num_planes(),
+                planes.len()
+            ));
+        }
+        let mut uploads = Vec::with_capacity(self.textures.len());
+        for (plane, texture) in self.textures.iter() {
+            let format = texture.internal_format().external_format();
+            let texel = texture.internal_format().default_texel();
+            let unpack = plane_unpack(
+                *plane,
+                strides[*plane],
+                texture.width(),
+                texture.height(),
+                format.pixel_size(texel),
+                planes[*plane].len(),
+            )?;
+            uploads.push((*plane, texture, format, texel, unpack));
+        }
+        for (plane, texture, format, texel, unpack) in uploads.iter() {
+            let _a = AutoBinder::new(vec![*texture, unpack]);
+            texture.update_region(
+                0,
+                0,
+                texture.width(),
+                texture.height(),
+                *format,
+                *texel,
+                planes[*plane],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Draw the frame converted to RGB into `rect` of the `framebuffer`.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn draw_to(&self, framebuffer: &Framebuffer, rect: &Viewport) {
+        let slots: Vec<SlotBinder> = self
+            .textures
+            .iter()
+            .enumerate()
+            .map(|(i, (_, texture))| SlotBinder::new(texture, i as u32))
+            .collect();
+        let mut list: Vec<&dyn Bindable> = vec![framebuffer, rect, &self.program];
+        list.extend(slots.iter().map(|v| v as &dyn Bindable));
+        list.push(&self.vao);
+        let _a = AutoBinder::new(list);
+        crate::draw_arrays(gl::TRIANGLE_STRIP, 0, 4);
+    }
+
+    /// Returns the pixel format of the frames.
+    pub fn format(&self) -> VideoFormat {
+        self.format
+    }
+
+    /// Returns the width of the frames.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the frames.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the textures sampled as `u_plane0`, `u_plane1` and `u_plane2`.
+    pub fn textures(&self) -> impl Iterator<Item = &Texture<'a>> {
+        self.textures.iter().map(|(_, texture)| texture)
+    }
+}
+
+/// Returns the unpack parameters of the `plane` with rows of `stride` bytes,
+/// if the `len` bytes of the plane hold `width` x `height` pixels.
+fn plane_unpack(
+    plane: usize,
+    stride: usize,
+    width: usize,
+    height: usize,
+    pixel_size: usize,
+    len: usize,
+) -> Result<PixelUnpack, String> {
+    if stride < width * pixel_size {
+        return Err(format!(
+            "Invalid stride {} of plane {} with {} pixels of {} bytes!",
+            stride, plane, width, pixel_size
+        ));
+    }
+    let unpack = PixelUnpack::with_stride(stride, pixel_size)?;
+    let size = unpack.required_size(width, height, pixel_size);
+    if len < size {
+        return Err(format!(
+            "Plane {} requires {} bytes, but got {}!",
+            plane, size, len
+        ));
+    }
+    Ok(unpack)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(matrix: YuvMatrix, range: YuvRange, yuv: [GLfloat; 3]) -> [GLfloat; 3] {
+        let (m, o) = yuv_to_rgb(matrix, range);
+        let v = [yuv[0] - o[0], yuv[1] - o[1], yuv[2] - o[2]];
+        let mut rgb = [0.0; 3];
+        for (row, c) in rgb.iter_mut().enumerate() {
+            *c = m[row] * v[0] + m[3 + row] * v[1] + m[6 + row] * v[2];
+        }
+        rgb
+    }
+
+    #[test]
+    fn test_yuv_to_rgb() {
+        let gray = 128.0 / 255.0;
+        for rgb in [
+            convert(YuvMatrix::Bt601, YuvRange::Full, [1.0, gray, gray]),
+            convert(
+                YuvMatrix::Bt709,
+                YuvRange::Limited,
+                [235.0 / 255.0, gray, gray],
+            ),
+        ]
+        .iter()
+        {
+            assert!(rgb.iter().all(|c| (c - 1.0).abs() < 1e-4), "{:?}", rgb);
+        }
+        // Pure red in BT.601 full range.
+        let rgb = convert(YuvMatrix::Bt601, YuvRange::Full, [0.299, 0.331264, 1.0]);
+        assert!((rgb[0] - 1.0).abs() < 1e-2 && rgb[1].abs() < 1e-2 && rgb[2].abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_plane_unpack() {
+        // NV12 chroma plane of 3x2 pixels of 2 bytes with rows of 8 bytes.
+        assert!(plane_unpack(1, 8, 3, 2, 2, 8 + 6).is_ok());
+        assert!(plane_unpack(1, 8, 3, 2, 2, 8 + 5).is_err());
+        assert!(plane_unpack(1, 4, 3, 2, 2, 64).is_err());
+        assert!(plane_unpack(1, 7, 3, 2, 2, 64).is_err());
+    }
+}
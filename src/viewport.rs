@@ -1,6 +1,11 @@
-use crate::prelude::Bindable;
+use crate::{gl, prelude::Bindable, GLfloat, Matrix4, StateCache, Vector2};
+use std::cell::RefCell;
 
-#[derive(Clone, Copy, Default, Debug)]
+thread_local! {
+    static SAVED_DEPTH_RANGES: RefCell<Vec<DepthRange>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 pub struct Viewport {
     pub x: i32,
     pub y: i32,
@@ -33,12 +38,197 @@ impl Viewport {
         self.w = w;
         self.h = h;
     }
+
+    /// Returns the largest viewport centered in the drawable of
+    /// `drawable_w` x `drawable_h` pixels which keeps the aspect ratio of
+    /// the content of `content_w` x `content_h`.
+    ///
+    /// Wider content gets bars above and below (letterbox), narrower content
+    /// gets bars on the left and right (pillarbox).
+    pub fn fit(content_w: i32, content_h: i32, drawable_w: i32, drawable_h: i32) -> Self {
+        if content_w <= 0 || content_h <= 0 {
+            return Self::with_size(drawable_w, drawable_h);
+        }
+        let (cw, ch) = (content_w as i64, content_h as i64);
+        let (dw, dh) = (drawable_w as i64, drawable_h as i64);
+        let (w, h) = if cw * dh > dw * ch {
+            (dw, dw * ch / cw)
+        } else {
+            (dh * cw / ch, dh)
+        };
+        Self::with_offset(
+            ((dw - w) / 2) as i32,
+            ((dh - h) / 2) as i32,
+            w as i32,
+            h as i32,
+        )
+    }
+
+    /// Returns the aspect ratio, width divided by height.
+    pub fn aspect(&self) -> GLfloat {
+        if self.h == 0 {
+            0.0
+        } else {
+            self.w as GLfloat / self.h as GLfloat
+        }
+    }
+
+    /// Convert from logical pixels to physical pixels with the display `scale`
+    /// factor, e.g. `2.0` on a HiDPI display.
+    pub fn to_physical(&self, scale: GLfloat) -> Self {
+        self.scaled(scale)
+    }
+
+    /// Convert from physical pixels to logical pixels with the display `scale`
+    /// factor.
+    pub fn to_logical(&self, scale: GLfloat) -> Self {
+        self.scaled(1.0 / scale)
+    }
+
+    fn scaled(&self, scale: GLfloat) -> Self {
+        let x = (self.x as GLfloat * scale).round() as i32;
+        let y = (self.y as GLfloat * scale).round() as i32;
+        let right = ((self.x + self.w) as GLfloat * scale).round() as i32;
+        let top = ((self.y + self.h) as GLfloat * scale).round() as i32;
+        Self::with_offset(x, y, right - x, top - y)
+    }
+
+    /// Returns `true` if the point in GL window coordinates is inside.
+    pub fn contains(&self, x: GLfloat, y: GLfloat) -> bool {
+        x >= self.x as GLfloat
+            && y >= self.y as GLfloat
+            && x < (self.x + self.w) as GLfloat
+            && y < (self.y + self.h) as GLfloat
+    }
+
+    /// Convert the point from window coordinates, with origin at the top-left
+    /// of the drawable of `drawable_h` pixels high, to NDC of the viewport.
+    ///
+    /// The coordinates are physical pixels, multiply logical ones by the
+    /// scale factor first.
+    pub fn window_to_ndc(&self, x: GLfloat, y: GLfloat, drawable_h: i32) -> Vector2 {
+        let y = drawable_h as GLfloat - y;
+        Vector2::new(
+            (x - self.x as GLfloat) / self.w as GLfloat * 2.0 - 1.0,
+            (y - self.y as GLfloat) / self.h as GLfloat * 2.0 - 1.0,
+        )
+    }
+
+    /// Convert the point from NDC of the viewport to window coordinates, with
+    /// origin at the top-left of the drawable of `drawable_h` pixels high.
+    pub fn ndc_to_window(&self, ndc: &Vector2, drawable_h: i32) -> Vector2 {
+        let x = (ndc.x + 1.0) * 0.5 * self.w as GLfloat + self.x as GLfloat;
+        let y = (ndc.y + 1.0) * 0.5 * self.h as GLfloat + self.y as GLfloat;
+        Vector2::new(x, drawable_h as GLfloat - y)
+    }
+
+    /// Returns the orthographic projection which maps the pixels of the
+    /// viewport to NDC, with origin at the top-left and Y pointing down.
+    pub fn ortho(&self) -> Matrix4 {
+        Matrix4::new_orthographic(0.0, self.w as GLfloat, self.h as GLfloat, 0.0, -1.0, 1.0)
+    }
 }
 
 impl Bindable for Viewport {
     fn bind(&self) {
         if (self.w * self.h) > 0 {
-            crate::viewport(self.x, self.y, self.w, self.h);
+            StateCache::with(|cache| cache.viewport(self.x, self.y, self.w, self.h));
         }
     }
+
+    fn unbind(&self) {
+        StateCache::with(|cache| cache.restore_viewport());
+    }
+}
+
+/// Mapping of the depth values from NDC to window coordinates, binding sets
+/// the range and unbinding restores the previous one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthRange {
+    pub near: GLfloat,
+    pub far: GLfloat,
+}
+
+impl DepthRange {
+    pub fn new(near: GLfloat, far: GLfloat) -> Self {
+        Self { near, far }
+    }
+
+    /// Query the current depth range of the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn current() -> Self {
+        let mut range: [GLfloat; 2] = [0.0, 1.0];
+        crate::get_floatv(gl::DEPTH_RANGE, &mut range);
+        Self::new(range[0], range[1])
+    }
+
+    /// Apply the depth range to the context.
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn apply(&self) {
+        crate::depth_rangef(self.near, self.far);
+    }
+}
+
+impl Default for DepthRange {
+    fn default() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+impl Bindable for DepthRange {
+    fn bind(&self) {
+        let previous = DepthRange::current();
+        SAVED_DEPTH_RANGES.with(|saved| saved.borrow_mut().push(previous));
+        self.apply();
+    }
+
+    fn unbind(&self) {
+        if let Some(previous) = SAVED_DEPTH_RANGES.with(|saved| saved.borrow_mut().pop()) {
+            previous.apply();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector4;
+
+    #[test]
+    fn test_fit() {
+        // 16:9 video on a 4:3 panel is letterboxed.
+        assert_eq!(
+            Viewport::fit(1920, 1080, 640, 480),
+            Viewport::with_offset(0, 60, 640, 360)
+        );
+        // 4:3 video on a 16:9 panel is pillarboxed.
+        assert_eq!(
+            Viewport::fit(640, 480, 1920, 1080),
+            Viewport::with_offset(240, 0, 1440, 1080)
+        );
+        assert_eq!(Viewport::fit(0, 0, 800, 600), Viewport::with_size(800, 600));
+    }
+
+    #[test]
+    fn test_conversions() {
+        let viewport = Viewport::with_offset(0, 60, 640, 360);
+        let ndc = viewport.window_to_ndc(320.0, 240.0, 480);
+        assert!(ndc.norm() < 1e-6);
+        let ndc = viewport.window_to_ndc(0.0, 60.0, 480);
+        assert!((ndc - Vector2::new(-1.0, 1.0)).norm() < 1e-6);
+        let window = viewport.ndc_to_window(&Vector2::new(1.0, -1.0), 480);
+        assert!((window - Vector2::new(640.0, 420.0)).norm() < 1e-6);
+
+        assert_eq!(
+            Viewport::with_offset(10, 20, 300, 200).to_physical(1.5),
+            Viewport::with_offset(15, 30, 450, 300)
+        );
+        assert_eq!(
+            Viewport::with_offset(15, 30, 450, 300).to_logical(1.5),
+            Viewport::with_offset(10, 20, 300, 200)
+        );
+
+        let top_left = viewport.ortho() * Vector4::new(0.0, 0.0, 0.0, 1.0);
+        assert!((top_left - Vector4::new(-1.0, 1.0, 0.0, 1.0)).norm() < 1e-6);
+    }
 }